@@ -5,10 +5,7 @@
 //! Or with custom configuration:
 //! LYCENTO_BASE_URL=https://api.lycento.com LYCENTO_API_KEY=your-key cargo run --example basic-usage
 
-use lycento_sdk::{
-    ActivateOptions, DeactivateOptions, DeviceInfo, LycentoClient, LycentoConfig, Platform,
-    ValidateOptions,
-};
+use lycento_sdk::{ActivateOptions, DeviceInfo, LycentoClient, LycentoConfig};
 use std::env;
 
 #[tokio::main]
@@ -52,7 +49,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     match client
         .activate(
             ActivateOptions::new(&license_key)
-                .with_platform(device_info.platform.clone()),
+                .with_platform(device_info.platform),
         )
         .await
     {
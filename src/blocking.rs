@@ -0,0 +1,522 @@
+//! Blocking client for the Lycento SDK.
+//!
+//! This module provides `LycentoClientBlocking`, a synchronous counterpart of
+//! `LycentoClient` built on `reqwest::blocking::Client`. It shares the option
+//! and response types with the async client and is only available with the
+//! `blocking` feature. Enabling `blocking` alongside the async client is fine,
+//! since reqwest supports both at the same time.
+//!
+//! The blocking client must not be used from within an async runtime.
+
+use crate::client::{
+    activation_payload, configure_client_builder, default_device_id, finish_deactivation,
+    handle_activation_response, handle_error_response, handle_network_error, handle_renewal_response,
+    handle_validation_response, header_map, legacy_deactivation_fallback, legacy_fallback, normalize_api_prefix, normalize_base_url,
+    normalize_fallback_url, parse_body, parse_license, parse_response, verify_signature, with_attempts, ActivateOptions,
+    ActivateResponse, AuthScheme, DeactivateOptions, Failover, DeactivateResponse, EXCLUDE_ACTIVATIONS, LicenseInfo,
+    LicenseInfoResponse, LycentoConfig, RenewOptions, RenewResponse, RequestObserver, RequestOptions, SIGNATURE_HEADER,
+    ValidateOptions, ValidateResponse,
+};
+use crate::dry_run::{query_json, DryRun};
+use crate::errors::{ActivationError, DeactivationError, LycentoError, ValidationError};
+use crate::logging::{log_retry, record_span_field, redact_license_key, trace_body, RequestSpan};
+use crate::retry::{backoff_delay, is_retryable_error, is_retryable_status, parse_retry_after, retry_delay};
+use reqwest::blocking::{Client, Response};
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use std::io::Read;
+use std::thread;
+use std::time::Instant;
+use uuid::Uuid;
+
+/// Synchronous Lycento client for license operations.
+pub struct LycentoClientBlocking {
+    client: Client,
+    base_url: String,
+    failover: Option<Failover>,
+    api_prefix: String,
+    api_key: Option<String>,
+    auth_scheme: AuthScheme,
+    headers: HeaderMap,
+    request_observer: Option<RequestObserver>,
+    max_retries: u32,
+    retry_base_delay: u64,
+    redact_license_keys: bool,
+    device_id: Option<String>,
+    legacy_device_ids: bool,
+    max_response_bytes: usize,
+    response_secret: Option<Vec<u8>>,
+    dry_run: Option<DryRun>,
+}
+
+impl std::fmt::Debug for LycentoClientBlocking {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LycentoClientBlocking")
+            .field("client", &self.client)
+            .field("base_url", &self.base_url)
+            .field("failover", &self.failover)
+            .field("api_prefix", &self.api_prefix)
+            .field("api_key", &self.api_key)
+            .field("auth_scheme", &self.auth_scheme)
+            .field("headers", &self.headers)
+            .field("request_observer", &self.request_observer)
+            .field("max_retries", &self.max_retries)
+            .field("retry_base_delay", &self.retry_base_delay)
+            .field("redact_license_keys", &self.redact_license_keys)
+            .field("device_id", &self.device_id)
+            .field("legacy_device_ids", &self.legacy_device_ids)
+            .field("max_response_bytes", &self.max_response_bytes)
+            .field("response_secret", &self.response_secret.as_ref().map(|_| "<redacted>"))
+            .field("dry_run", &self.dry_run)
+            .finish()
+    }
+}
+
+impl LycentoClientBlocking {
+    /// Create a new blocking client with the given configuration.
+    pub fn new(config: LycentoConfig) -> Result<Self, LycentoError> {
+        let client = configure_client_builder!(Client::builder(), &config)
+            .build()
+            .map_err(LycentoError::from)?;
+
+        Self::with_client(client, config)
+    }
+
+    /// Create a new blocking client reusing an existing `reqwest::blocking::Client`.
+    ///
+    /// The `timeout` from `config` is ignored since the provided client owns
+    /// its own timeout.
+    pub fn with_client(client: Client, config: LycentoConfig) -> Result<Self, LycentoError> {
+        if config.token_provider.is_some() {
+            return Err(LycentoError::InvalidConfig(
+                "token providers are async and need LycentoClient".to_string(),
+            ));
+        }
+
+        let base_url = normalize_base_url(&config.base_url)?;
+        let fallback_url = config.fallback_url.as_deref().map(normalize_fallback_url).transpose()?;
+        let headers = header_map(&config)?;
+
+        Ok(Self {
+            client,
+            base_url,
+            failover: fallback_url.map(Failover::new),
+            api_prefix: normalize_api_prefix(&config.api_prefix),
+            api_key: config.api_key,
+            auth_scheme: config.auth_scheme,
+            headers,
+            max_retries: config.max_retries,
+            retry_base_delay: config.retry_base_delay,
+            redact_license_keys: config.redact_license_keys,
+            device_id: config.device_id,
+            legacy_device_ids: config.legacy_device_ids,
+            max_response_bytes: config.max_response_bytes,
+            response_secret: config.response_secret,
+            request_observer: config.request_observer,
+            dry_run: config.dry_run.then(|| DryRun::new(config.dry_run_responses)),
+        })
+    }
+
+    /// Activate a license on the current device.
+    pub fn activate(&self, options: ActivateOptions) -> Result<ActivateResponse, ActivationError> {
+        let request = RequestOptions {
+            timeout: options.timeout,
+            // Generated once per call so that retries reuse the same key.
+            idempotency_key: Some(
+                options
+                    .idempotency_key
+                    .clone()
+                    .unwrap_or_else(|| Uuid::new_v4().to_string()),
+            ),
+        };
+        let payload = self.activation_payload(&options)?;
+
+        let response = self
+            .post("/licenses/activate", payload, &request)
+            .map_err(ActivationError::from)?;
+
+        handle_activation_response(response)
+    }
+
+    /// The JSON body [`activate`](Self::activate) would post for `options`,
+    /// with device defaults resolved, without sending anything.
+    ///
+    /// Fails only if no device ID is given and none can be derived (see the
+    /// `fingerprint` feature).
+    pub fn activation_payload(&self, options: &ActivateOptions) -> Result<serde_json::Value, LycentoError> {
+        let device_id = options.device_id.clone().map_or_else(|| self.device_id(), Ok)?;
+        Ok(activation_payload(options.clone(), device_id))
+    }
+
+    /// Activate with a license key string (uses default device).
+    pub fn activate_license(&self, license_key: &str) -> Result<ActivateResponse, ActivationError> {
+        self.activate(ActivateOptions::new(license_key))
+    }
+
+    /// Validate a license.
+    pub fn validate(&self, options: ValidateOptions) -> Result<ValidateResponse, ValidationError> {
+        let request = RequestOptions::with_timeout(options.timeout);
+        let device_id = options.device_id.map_or_else(|| self.device_id(), Ok)?;
+
+        let payload = serde_json::json!({
+            "license_key": options.license_key,
+            "device_id": device_id,
+        });
+
+        let response = self
+            .post("/licenses/validate", payload, &request)
+            .map_err(ValidationError::from)?;
+
+        let response = handle_validation_response(response)?;
+
+        if let Some(legacy_id) = legacy_fallback(self.legacy_device_ids, &device_id, &response) {
+            log::debug!("Device not activated as {}, retrying with legacy ID", device_id);
+            let payload = serde_json::json!({
+                "license_key": options.license_key,
+                "device_id": legacy_id,
+            });
+
+            let legacy = self
+                .post("/licenses/validate", payload, &request)
+                .map_err(ValidationError::from)?;
+            let legacy = handle_validation_response(legacy)?;
+            if legacy.valid {
+                return Ok(legacy);
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Validate a license key (uses default device).
+    pub fn validate_license(&self, license_key: &str) -> Result<ValidateResponse, ValidationError> {
+        self.validate(ValidateOptions::new(license_key))
+    }
+
+    /// Quick check if a license is valid.
+    pub fn is_valid(&self, license_key: &str) -> bool {
+        self.validate_license(license_key)
+            .map(|r| r.valid)
+            .unwrap_or(false)
+    }
+
+    /// Deactivate a license on a specific device.
+    pub fn deactivate(&self, options: DeactivateOptions) -> Result<DeactivateResponse, DeactivationError> {
+        let response = self.send_deactivation(&options);
+        finish_deactivation(&options, response)
+    }
+
+    /// Deactivate the current device.
+    ///
+    /// If the device has no activation under its ID, the deactivation is
+    /// retried with the ID an earlier release generated on this machine,
+    /// unless disabled with [`LycentoConfig::with_legacy_device_ids`].
+    pub fn deactivate_current(&self, license_key: &str) -> Result<DeactivateResponse, DeactivationError> {
+        let options = DeactivateOptions::new(license_key, self.device_id()?);
+        let response = self.send_deactivation(&options);
+
+        if let Some(legacy_id) = legacy_deactivation_fallback(self.legacy_device_ids, &options.device_id, &response) {
+            log::debug!("No activation for {}, retrying with legacy ID", options.device_id);
+            return self.deactivate(DeactivateOptions::new(license_key, legacy_id));
+        }
+
+        finish_deactivation(&options, response)
+    }
+
+    fn send_deactivation(&self, options: &DeactivateOptions) -> Result<serde_json::Value, LycentoError> {
+        let request = RequestOptions::with_timeout(options.timeout);
+        let payload = serde_json::json!({
+            "license_key": options.license_key,
+            "device_id": options.device_id,
+        });
+
+        self.post("/licenses/deactivate", payload, &request)
+    }
+
+    /// Renew a subscription license.
+    pub fn renew(&self, options: RenewOptions) -> Result<RenewResponse, LycentoError> {
+        let request = RequestOptions::with_timeout(options.timeout);
+        let payload = serde_json::json!({
+            "license_key": options.license_key,
+            "payment_token": options.payment_token,
+        });
+
+        let response = self.post("/licenses/renew", payload, &request)?;
+
+        handle_renewal_response(response)
+    }
+
+    /// Get license information including all activations.
+    pub fn get_info(&self, license_key: &str) -> Result<LicenseInfoResponse, LycentoError> {
+        let json = self.get("/licenses/info", license_key, &[])?;
+        parse_response(json)
+    }
+
+    /// Get only the license details, without its activations.
+    pub fn get_license(&self, license_key: &str) -> Result<LicenseInfo, LycentoError> {
+        let json = self.get("/licenses/info", license_key, &[EXCLUDE_ACTIVATIONS])?;
+        parse_license(json)
+    }
+
+    /// Check a license without touching its activations.
+    ///
+    /// See [`LycentoClient::check_license`](crate::LycentoClient::check_license).
+    pub fn check_license(&self, license_key: &str) -> Result<LicenseInfo, LycentoError> {
+        self.get_license(license_key)
+    }
+
+    // Private helper methods
+
+    /// Send a GET request with the license key and `query` as parameters.
+    fn get(&self, path: &str, license_key: &str, query: &[(&str, &str)]) -> Result<serde_json::Value, LycentoError> {
+        let _span = RequestSpan::new(path).enter();
+        record_span_field("license_key", &self.loggable_key(license_key));
+
+        if let Some(ref dry_run) = self.dry_run {
+            let url = self.url_at(&self.base_url, path);
+            let request = query_json(license_key, query);
+            return Ok(dry_run.respond("GET", &url, path, &request, self.redact_license_keys));
+        }
+
+        let mut base_url = self.primary_or_fallback();
+
+        let (url, response) = loop {
+            let url = self.url_at(base_url, path);
+            log::debug!("GET {}?license_key={}", url, self.loggable_key(license_key));
+
+            let request = self.client.get(&url).query(&[("license_key", license_key)]).query(query);
+            let started = Instant::now();
+            let result = self.authorize(request).send();
+            self.observe(base_url, path, 1, started, result.as_ref().map(Response::status));
+
+            match result {
+                Ok(response) => break (url, response),
+                Err(e) => match self.failover_url(base_url, &e, true) {
+                    Some(fallback) => base_url = fallback,
+                    None => return Err(e.into()),
+                },
+            }
+        };
+
+        let status = response.status();
+        log::debug!("GET {} -> {}", url, status);
+        record_span_field("status", status.as_str());
+
+        let retry_after = parse_retry_after(response.headers());
+        let json = read_json(response, self.max_response_bytes, self.response_secret.as_deref())?;
+        trace_body("Response body", &json, self.redact_license_keys);
+
+        if status.is_success() {
+            Ok(json)
+        } else {
+            Err(handle_error_response(status, &json, retry_after))
+        }
+    }
+
+    /// Device ID used when a call doesn't specify one.
+    fn device_id(&self) -> Result<String, LycentoError> {
+        self.device_id.clone().map_or_else(default_device_id, Ok)
+    }
+
+    fn loggable_key(&self, license_key: &str) -> String {
+        if self.redact_license_keys {
+            redact_license_key(license_key)
+        } else {
+            license_key.to_string()
+        }
+    }
+
+    fn url_at(&self, base_url: &str, path: &str) -> String {
+        format!("{}{}{}", base_url, self.api_prefix, path)
+    }
+
+    /// The server to send a new request to.
+    fn primary_or_fallback(&self) -> &str {
+        self.failover.as_ref().map_or(&self.base_url, |failover| failover.base_url(&self.base_url))
+    }
+
+    /// The fallback server to retry on after `error`, if any.
+    fn failover_url(&self, base_url: &str, error: &reqwest::Error, idempotent: bool) -> Option<&str> {
+        self.failover.as_ref()?.fail_over(base_url, &self.base_url, error, idempotent)
+    }
+
+    fn authorize(&self, request: reqwest::blocking::RequestBuilder) -> reqwest::blocking::RequestBuilder {
+        let request = request.headers(self.headers.clone());
+        match self.api_key.as_deref().and_then(|key| self.auth_scheme.header(key)) {
+            Some((name, value)) => request.header(name, value),
+            None => request,
+        }
+    }
+
+    fn post(
+        &self,
+        path: &str,
+        payload: serde_json::Value,
+        options: &RequestOptions,
+    ) -> Result<serde_json::Value, LycentoError> {
+        let _span = RequestSpan::new(path).enter();
+        record_span_field("license_key", &self.loggable_key(payload["license_key"].as_str().unwrap_or_default()));
+        if let Some(device_id) = payload["device_id"].as_str() {
+            record_span_field("device_id", device_id);
+        }
+
+        if let Some(ref dry_run) = self.dry_run {
+            let url = self.url_at(&self.base_url, path);
+            return Ok(dry_run.respond("POST", &url, path, &payload, self.redact_license_keys));
+        }
+
+        let max_attempts = self.max_retries.saturating_add(1);
+        let mut attempt = 0;
+        let mut base_url = self.primary_or_fallback();
+
+        loop {
+            attempt += 1;
+            let can_retry = attempt < max_attempts;
+            let url = self.url_at(base_url, path);
+
+            log::debug!("POST {} (attempt {}/{})", url, attempt, max_attempts);
+            trace_body("Request body", &payload, self.redact_license_keys);
+
+            let mut request = self.authorize(self.client.post(&url).json(&payload));
+            if let Some(timeout) = options.timeout {
+                request = request.timeout(timeout);
+            }
+            if let Some(ref key) = options.idempotency_key {
+                request = request.header("Idempotency-Key", key);
+            }
+
+            let started = Instant::now();
+            let result = request.send();
+            self.observe(base_url, path, attempt, started, result.as_ref().map(Response::status));
+
+            match result {
+                Ok(response) => {
+                    log::debug!("POST {} -> {}", url, response.status());
+
+                    if can_retry && is_retryable_status(response.status()) {
+                        let delay = retry_delay(response.headers(), self.retry_base_delay, attempt);
+                        log_retry(&url, attempt, delay, response.status().as_str());
+                        thread::sleep(delay);
+                        continue;
+                    }
+
+                    return self
+                        .handle_response(response)
+                        .map_err(|e| with_attempts(e, attempt));
+                }
+                Err(e) => {
+                    log::debug!("POST {} failed: {}", url, e);
+
+                    if let Some(fallback) = self.failover_url(base_url, &e, false) {
+                        // The failover doesn't use up a retry.
+                        base_url = fallback;
+                        attempt -= 1;
+                        continue;
+                    }
+
+                    if can_retry && is_retryable_error(&e) {
+                        let delay = backoff_delay(self.retry_base_delay, attempt);
+                        log_retry(&url, attempt, delay, &e.to_string());
+                        thread::sleep(delay);
+                        continue;
+                    }
+
+                    return Err(with_attempts(handle_network_error(e), attempt));
+                }
+            }
+        }
+    }
+
+    /// Report a request attempt to the configured observer, if any.
+    fn observe(
+        &self,
+        base_url: &str,
+        path: &str,
+        attempt: u32,
+        started: Instant,
+        outcome: Result<StatusCode, &reqwest::Error>,
+    ) {
+        if let Some(ref observer) = self.request_observer {
+            observer.notify(base_url, path, attempt, started.elapsed(), outcome);
+        }
+    }
+
+    fn handle_response(&self, response: Response) -> Result<serde_json::Value, LycentoError> {
+        let status = response.status();
+        record_span_field("status", status.as_str());
+        let retry_after = parse_retry_after(response.headers());
+        let json = read_json(response, self.max_response_bytes, self.response_secret.as_deref())?;
+        trace_body("Response body", &json, self.redact_license_keys);
+
+        if status.is_success() {
+            Ok(json)
+        } else {
+            Err(handle_error_response(status, &json, retry_after))
+        }
+    }
+}
+
+/// Read a JSON response body of at most `limit` bytes.
+///
+/// With a `secret`, the body of a successful response must match its
+/// `X-Signature` header.
+fn read_json(
+    response: reqwest::blocking::Response,
+    limit: usize,
+    secret: Option<&[u8]>,
+) -> Result<serde_json::Value, LycentoError> {
+    let status = response.status();
+    let secret = secret.filter(|_| status.is_success());
+    let signature = response.headers().get(SIGNATURE_HEADER).cloned();
+    let mut body = Vec::new();
+    response
+        .take(limit as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| LycentoError::wrap(e.to_string(), e))?;
+    if body.len() > limit {
+        return Err(LycentoError::ResponseTooLarge { limit });
+    }
+    if let Some(secret) = secret {
+        verify_signature(secret, signature.as_ref(), &body)?;
+    }
+
+    parse_body(status, &body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocking_validate() {
+        let mut server = mockito::Server::new();
+        let ok = server
+            .mock("POST", "/api/v1/licenses/validate")
+            .with_status(200)
+            .with_body(
+                r#"{
+                    "valid": true,
+                    "license": {
+                        "key": "TEST-KEY",
+                        "status": "active",
+                        "type": "perpetual",
+                        "expiresAt": null,
+                        "maxDevices": 3
+                    }
+                }"#,
+            )
+            .create();
+
+        let client = LycentoClientBlocking::new(LycentoConfig::new(server.url())).unwrap();
+        assert!(client.validate_license("TEST-KEY").unwrap().valid);
+        ok.assert();
+    }
+
+    #[test]
+    fn test_blocking_debug_redacts_response_secret() {
+        let config = LycentoConfig::new("https://lycento.test").with_response_secret("hmac-secret");
+
+        let debug = format!("{:?}", LycentoClientBlocking::new(config).unwrap());
+        assert!(debug.contains(r#"response_secret: Some("<redacted>")"#));
+    }
+}
@@ -0,0 +1,101 @@
+//! In-memory cache of validation results.
+//!
+//! When enabled with `LycentoConfig::with_validation_cache_ttl`, successful
+//! validations are kept per `(license_key, device_id)` for the configured TTL
+//! so that repeated checks don't each hit the server. The cache is shared by
+//! all clones of a client.
+
+use crate::client::ValidateResponse;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Validation results keyed by license key and device ID.
+#[derive(Debug)]
+pub(crate) struct ValidationCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<(String, String), (Instant, ValidateResponse)>>,
+}
+
+impl ValidationCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The cached response for `license_key` on `device_id`, if still fresh.
+    pub(crate) fn get(&self, license_key: &str, device_id: &str) -> Option<ValidateResponse> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (license_key.to_string(), device_id.to_string());
+
+        match entries.get(&key) {
+            Some((stored_at, response)) if stored_at.elapsed() < self.ttl => Some(response.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, license_key: &str, device_id: &str, response: &ValidateResponse) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert((license_key.to_string(), device_id.to_string()), (Instant::now(), response.clone()));
+    }
+
+    /// Drop the cached responses for `license_key` on every device.
+    pub(crate) fn invalidate(&self, license_key: &str) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|(key, _), _| key != license_key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response() -> ValidateResponse {
+        serde_json::from_value(serde_json::json!({
+            "valid": true,
+            "license": {
+                "key": "TEST-KEY",
+                "status": "active",
+                "type": "perpetual",
+                "expiresAt": null,
+                "maxDevices": 3
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_entries_expire_after_ttl() {
+        let cache = ValidationCache::new(Duration::from_millis(20));
+        cache.insert("TEST-KEY", "device-1", &response());
+
+        assert_eq!(cache.get("TEST-KEY", "device-1"), Some(response()));
+        assert_eq!(cache.get("TEST-KEY", "device-2"), None);
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(cache.get("TEST-KEY", "device-1"), None);
+    }
+
+    #[test]
+    fn test_invalidate_drops_all_devices() {
+        let cache = ValidationCache::new(Duration::from_secs(60));
+        cache.insert("TEST-KEY", "device-1", &response());
+        cache.insert("TEST-KEY", "device-2", &response());
+        cache.insert("OTHER-KEY", "device-1", &response());
+
+        cache.invalidate("TEST-KEY");
+        assert_eq!(cache.get("TEST-KEY", "device-1"), None);
+        assert_eq!(cache.get("TEST-KEY", "device-2"), None);
+        assert!(cache.get("OTHER-KEY", "device-1").is_some());
+    }
+}
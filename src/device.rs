@@ -2,16 +2,42 @@
 //!
 //! This module provides functionality to gather device information for license activation,
 //! including device ID generation, platform detection, and device name resolution.
+//!
+//! On WASM there is no access to system information: the platform and name are
+//! reported as unknown and the device ID is random per page load, so callers
+//! should persist their own ID and pass it to `LycentoConfig::with_device_id`.
+//!
+//! Without the `fingerprint` feature (on by default) nothing identifying is
+//! read from the machine: the hostname, MAC address and machine ID are never
+//! collected, no virtual machine probes run, and the functions that derive a
+//! device ID from them ([`get_device_id`], [`generate_device_id`],
+//! [`DeviceIdBuilder`], [`get_machine_id`], [`get_device_info`],
+//! [`is_virtual_machine`], ...) are not compiled. Callers must supply a
+//! device ID, e.g. from [`get_persistent_device_id`].
 
+#[cfg(not(target_arch = "wasm32"))]
+use directories::ProjectDirs;
+#[cfg(feature = "fingerprint")]
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
 use std::hash::{Hash, Hasher};
-use sys_info;
+use std::io;
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+use std::path::{Path, PathBuf};
+#[cfg(feature = "fingerprint")]
+use std::sync::RwLock;
+#[cfg(all(feature = "fingerprint", any(target_os = "macos", target_os = "windows")))]
+use std::process::Command;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
 /// Supported platforms for license activation.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Platform {
     #[serde(rename = "windows")]
@@ -25,6 +51,7 @@ pub enum Platform {
     #[serde(rename = "ios")]
     Ios,
     #[serde(rename = "unknown")]
+    #[default]
     Unknown,
 }
 
@@ -42,9 +69,83 @@ impl Platform {
     }
 }
 
-impl Default for Platform {
-    fn default() -> Self {
-        Self::Unknown
+/// Parses the API value, e.g. `macos`, ignoring case. Anything unrecognized
+/// becomes [`Platform::Unknown`].
+impl From<&str> for Platform {
+    fn from(platform: &str) -> Self {
+        match platform.trim().to_ascii_lowercase().as_str() {
+            "windows" => Platform::Windows,
+            "macos" => Platform::Macos,
+            "linux" => Platform::Linux,
+            "android" => Platform::Android,
+            "ios" => Platform::Ios,
+            _ => Platform::Unknown,
+        }
+    }
+}
+
+/// Displays the platform's product name, e.g. `macOS`. Use
+/// [`Platform::as_str`] for the value sent to the API.
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Platform::Windows => "Windows",
+            Platform::Macos => "macOS",
+            Platform::Linux => "Linux",
+            Platform::Android => "Android",
+            Platform::Ios => "iOS",
+            Platform::Unknown => "Unknown",
+        })
+    }
+}
+
+/// CPU architectures reported to the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Architecture {
+    #[serde(rename = "x86_64")]
+    X86_64,
+    #[serde(rename = "aarch64")]
+    Aarch64,
+    #[serde(rename = "x86")]
+    X86,
+    #[serde(rename = "arm")]
+    Arm,
+    #[serde(rename = "unknown")]
+    #[default]
+    Unknown,
+}
+
+impl Architecture {
+    /// Architecture the SDK was compiled for.
+    pub fn from_target() -> Self {
+        if cfg!(target_arch = "x86_64") {
+            Architecture::X86_64
+        } else if cfg!(target_arch = "aarch64") {
+            Architecture::Aarch64
+        } else if cfg!(target_arch = "x86") {
+            Architecture::X86
+        } else if cfg!(target_arch = "arm") {
+            Architecture::Arm
+        } else {
+            Architecture::Unknown
+        }
+    }
+
+    /// Convert architecture to string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Architecture::X86_64 => "x86_64",
+            Architecture::Aarch64 => "aarch64",
+            Architecture::X86 => "x86",
+            Architecture::Arm => "arm",
+            Architecture::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for Architecture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
     }
 }
 
@@ -61,67 +162,638 @@ pub struct DeviceInfo {
     /// Platform version/release.
     pub platform_version: String,
     /// System architecture.
-    pub architecture: String,
+    pub architecture: Architecture,
+    /// Whether the device appears to be a virtual machine, see
+    /// [`is_virtual_machine`]. Always `false` without the `fingerprint`
+    /// feature.
+    #[serde(rename = "virtual", default)]
+    pub is_virtual: bool,
+}
+
+impl DeviceInfo {
+    /// Deterministic JSON description of this device, for support tickets.
+    ///
+    /// Keys are sorted and compact. `deviceId` is left out, so the
+    /// fingerprint identifies the machine independently of how its ID was
+    /// generated and can be matched against the name and platform of
+    /// activation records.
+    pub fn fingerprint_json(&self) -> String {
+        let mut fields: BTreeMap<String, serde_json::Value> =
+            serde_json::from_value(serde_json::to_value(self).expect("DeviceInfo serializes to JSON"))
+                .expect("DeviceInfo serializes to a JSON object");
+        fields.remove("deviceId");
+        serde_json::to_string(&fields).expect("JSON values serialize")
+    }
+
+    /// SHA256 of [`fingerprint_json`](Self::fingerprint_json), hex-encoded.
+    pub fn fingerprint_hash(&self) -> String {
+        hash_string(&self.fingerprint_json())
+    }
 }
 
+/// Multi-line summary for support dialogs and logs, e.g.
+///
+/// ```text
+/// Workstation
+///   Platform: Linux 6.1 (x86_64)
+///   Device ID: v1:0123456789abcdef0123456789abcdef
+///   Virtual machine: no
+/// ```
+impl fmt::Display for DeviceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.device_name)?;
+        writeln!(f, "  Platform: {} {} ({})", self.platform, self.platform_version, self.architecture)?;
+        writeln!(f, "  Device ID: {}", self.device_id)?;
+        write!(f, "  Virtual machine: {}", if self.is_virtual { "yes" } else { "no" })
+    }
+}
+
+#[cfg(feature = "fingerprint")]
 impl Default for DeviceInfo {
     fn default() -> Self {
-        Self {
-            device_id: get_device_id(),
-            device_name: get_device_name(),
-            platform: get_platform(),
-            platform_version: get_platform_version(),
-            architecture: get_architecture(),
-        }
+        get_device_info()
     }
 }
 
-/// Cached device ID for performance.
-static CACHED_DEVICE_ID: Lazy<String> = Lazy::new(generate_device_id);
+/// Cached device ID for performance, replaced by [`refresh_device_id`].
+#[cfg(feature = "fingerprint")]
+static CACHED_DEVICE_ID: Lazy<RwLock<String>> = Lazy::new(|| RwLock::new(generate_device_id()));
+
+/// Environment variable that overrides the auto-detected device ID, for tests.
+pub(crate) const DEVICE_ID_ENV: &str = "LYCENTO_DEVICE_ID";
+
+/// File name used to store the persistent device ID.
+#[cfg(not(target_arch = "wasm32"))]
+const DEVICE_ID_FILE: &str = "device_id";
 
 /// Get the cached device ID.
+///
+/// The ID is derived from the hostname and OS information, so it needs no
+/// storage but changes if the machine is renamed or the OS is upgraded, which
+/// invalidates existing activations. Prefer [`get_persistent_device_id`] for
+/// new integrations; this function is kept for backward compatibility.
+///
+/// If the `LYCENTO_DEVICE_ID` environment variable is set, its value is
+/// returned verbatim instead. This is meant for CI and integration tests
+/// that need a stable identity without code changes; don't rely on it in
+/// production.
+#[cfg(feature = "fingerprint")]
 pub fn get_device_id() -> String {
-    CACHED_DEVICE_ID.clone()
+    if let Some(device_id) = device_id_from_env(DEVICE_ID_ENV) {
+        return device_id;
+    }
+    CACHED_DEVICE_ID.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Device ID set in the environment variable `name`, if it isn't empty.
+pub(crate) fn device_id_from_env(name: &str) -> Option<String> {
+    non_empty_device_id(std::env::var(name).ok())
+}
+
+fn non_empty_device_id(value: Option<String>) -> Option<String> {
+    value.filter(|device_id| !device_id.is_empty())
+}
+
+/// Recompute the cached device ID and return it.
+///
+/// [`get_device_id`] computes the ID once per process, so a hostname or OS
+/// change while the app is running isn't picked up until it restarts. This is
+/// rarely needed, and changing the ID detaches the device from its existing
+/// activations. It doesn't affect [`get_persistent_device_id`], which reads
+/// its file on every call.
+#[cfg(feature = "fingerprint")]
+pub fn refresh_device_id() -> String {
+    let device_id = generate_device_id();
+    *CACHED_DEVICE_ID.write().unwrap_or_else(|e| e.into_inner()) = device_id.clone();
+    device_id
+}
+
+/// A device ID loaded from (or written to) disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersistentDeviceId {
+    /// The device identifier.
+    pub device_id: String,
+    /// File the ID is stored in (`None` if it could not be persisted).
+    pub path: Option<PathBuf>,
+    /// Whether the ID was generated during this call.
+    pub first_run: bool,
+}
+
+/// Get a device ID that survives hostname and OS changes.
+///
+/// A random UUID is stored in the platform config directory for `app_name`
+/// (e.g. `~/.config/<app_name>` on Linux) and reused on later runs. If the
+/// file can't be read or written, this falls back to [`generate_device_id`]
+/// (a random ID without the `fingerprint` feature) and returns no path.
+pub fn get_persistent_device_id(app_name: &str) -> PersistentDeviceId {
+    #[cfg(not(target_arch = "wasm32"))]
+    let path = ProjectDirs::from("", "", app_name)
+        .map(|dirs| dirs.config_dir().join(DEVICE_ID_FILE));
+    #[cfg(target_arch = "wasm32")]
+    let path: Option<PathBuf> = {
+        let _ = app_name;
+        None
+    };
+
+    if let Some(path) = path {
+        if let Ok((device_id, first_run)) = load_or_create_device_id(&path) {
+            return PersistentDeviceId {
+                device_id,
+                path: Some(path),
+                first_run,
+            };
+        }
+    }
+
+    PersistentDeviceId {
+        device_id: fallback_device_id(),
+        path: None,
+        first_run: false,
+    }
+}
+
+/// Read the device ID stored at `path`, creating it if missing.
+///
+/// Returns the ID and whether it was newly created.
+fn load_or_create_device_id(path: &Path) -> io::Result<(String, bool)> {
+    if let Ok(contents) = fs::read_to_string(path) {
+        let device_id = contents.trim();
+        if !device_id.is_empty() {
+            return Ok((device_id.to_string(), false));
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let device_id = uuid::Uuid::new_v4().simple().to_string();
+    fs::write(path, &device_id)?;
+    Ok((device_id, true))
 }
 
 /// Generate a deterministic device ID from machine characteristics.
 ///
 /// Uses multiple system identifiers to create a unique but consistent
-/// device identifier that persists across restarts.
+/// device identifier that persists across restarts. On WASM, where none are
+/// available, a random ID is returned instead. Equivalent to
+/// `DeviceIdBuilder::default().build()`.
+#[cfg(feature = "fingerprint")]
 pub fn generate_device_id() -> String {
-    let mut hasher = Sha256::new();
+    DeviceIdBuilder::default().build()
+}
 
-    // Include multiple system identifiers for uniqueness
-    let hostname = sys_info::hostname().unwrap_or_default();
-    let os_type = sys_info::os_type().unwrap_or_default();
-    let os_release = sys_info::os_release().unwrap_or_default();
+/// Choose which system identifiers are hashed into a device ID.
+///
+/// The default uses the hostname, OS type, OS release and machine ID, and
+/// produces the same ID as [`generate_device_id`]. Fewer components make
+/// the ID more stable (e.g. across OS upgrades) but more likely to collide
+/// between machines; a salt keeps IDs from different apps unlinkable.
+///
+/// ```
+/// use lycento_sdk::DeviceIdBuilder;
+///
+/// let device_id = DeviceIdBuilder::default()
+///     .with_os_release(false)
+///     .with_salt("my-app")
+///     .build();
+/// assert!(device_id.starts_with("v1:"));
+/// ```
+#[cfg(feature = "fingerprint")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceIdBuilder {
+    hostname: bool,
+    os_type: bool,
+    os_release: bool,
+    machine_id: bool,
+    mac_address: bool,
+    salt: Option<String>,
+}
 
-    // Combine all identifiers
-    let combined = format!("{}-{}-{}", hostname, os_type, os_release);
-    hasher.update(combined.as_bytes());
+#[cfg(feature = "fingerprint")]
+impl Default for DeviceIdBuilder {
+    fn default() -> Self {
+        Self {
+            hostname: true,
+            os_type: true,
+            os_release: true,
+            machine_id: true,
+            mac_address: false,
+            salt: None,
+        }
+    }
+}
+
+#[cfg(feature = "fingerprint")]
+impl DeviceIdBuilder {
+    /// Include the hostname (default `true`).
+    pub fn with_hostname(mut self, enabled: bool) -> Self {
+        self.hostname = enabled;
+        self
+    }
+
+    /// Include the OS type, e.g. `Linux` (default `true`).
+    pub fn with_os_type(mut self, enabled: bool) -> Self {
+        self.os_type = enabled;
+        self
+    }
+
+    /// Include the OS release, which changes on OS upgrades (default `true`).
+    pub fn with_os_release(mut self, enabled: bool) -> Self {
+        self.os_release = enabled;
+        self
+    }
+
+    /// Include the OS machine identifier from [`get_machine_id`] when
+    /// available (default `true`).
+    pub fn with_machine_id(mut self, enabled: bool) -> Self {
+        self.machine_id = enabled;
+        self
+    }
+
+    /// Include the MAC address of the first network interface (default
+    /// `false`). Only read on Linux; ignored elsewhere.
+    pub fn with_mac_address(mut self, enabled: bool) -> Self {
+        self.mac_address = enabled;
+        self
+    }
+
+    /// Mix `salt` into the hash.
+    pub fn with_salt(mut self, salt: impl Into<String>) -> Self {
+        self.salt = Some(salt.into());
+        self
+    }
+
+    /// Hash the selected identifiers into a device ID.
+    ///
+    /// The ID is the algorithm version tag followed by 32 hex characters,
+    /// e.g. `v1:3f2a...`. On WASM, where no identifiers are available, a
+    /// random ID is returned.
+    ///
+    /// If none of the selected identifiers could be read, e.g. because all of
+    /// them are disabled, the machine ID is used instead (or the hostname,
+    /// where there is no machine ID), so that different machines don't share
+    /// one ID.
+    pub fn build(&self) -> String {
+        if cfg!(target_arch = "wasm32") {
+            return uuid::Uuid::new_v4().simple().to_string();
+        }
+
+        let mut components = Vec::new();
+        if self.hostname {
+            components.push(system::hostname().unwrap_or_default());
+        }
+        if self.os_type {
+            components.push(system::os_type().unwrap_or_default());
+        }
+        if self.os_release {
+            components.push(system::os_release().unwrap_or_default());
+        }
+        // Optional identifiers are only added when available
+        if self.machine_id {
+            components.extend(get_machine_id());
+        }
+        if self.mac_address {
+            components.extend(read_mac_address());
+        }
+        if components.iter().all(String::is_empty) {
+            components = get_machine_id().into_iter().collect();
+            if components.is_empty() {
+                components.push(system::hostname().unwrap_or_default());
+            }
+        }
+        components.extend(self.salt.clone());
+
+        let mut hasher = Sha256::new();
+        hasher.update(components.join("-").as_bytes());
+
+        // Take first 32 characters of hex hash
+        let result = hasher.finalize();
+        format!("v{}:{}", DEVICE_ID_VERSION, &hex::encode(result)[..32])
+    }
+}
+
+/// Version of the fingerprinting algorithm used by [`DeviceIdBuilder`].
+///
+/// Version 1 added the tag and the machine ID. The untagged IDs generated by
+/// earlier releases, which only hash the hostname, OS type and OS release,
+/// are treated as version 0.
+pub const DEVICE_ID_VERSION: u32 = 1;
+
+/// Get the algorithm version of a generated device ID.
+///
+/// Returns the version from a `v<N>:` tag, `Some(0)` for an untagged
+/// 32-character hex ID from an earlier release, and `None` for anything
+/// else, such as custom IDs.
+///
+/// Untagged IDs laid out as a random UUID, like the ones stored by
+/// [`get_persistent_device_id`], aren't treated as version 0. One in 64
+/// version 0 hashes happens to share that layout and is reported as `None`
+/// too.
+pub fn device_id_version(id: &str) -> Option<u32> {
+    match id.strip_prefix('v').and_then(|rest| rest.split_once(':')) {
+        Some((version, hash)) if is_device_hash(hash) => version.parse().ok(),
+        _ if is_device_hash(id) && !is_random_uuid(id) => Some(0),
+        _ => None,
+    }
+}
+
+/// Get the untagged ID an earlier release generated on this machine, if `id`
+/// is this machine's version 1 ID (see [`get_device_id`]).
+///
+/// Version 0 IDs can't be derived from a version 1 hash, so the ID is
+/// recomputed from this machine's hostname, OS type and OS release, exactly
+/// as earlier releases did. Returns `None` for any other ID, including
+/// version 1 IDs of other machines, and without the `fingerprint` feature
+/// or on WASM, where those identifiers aren't available. Used to keep
+/// activations made with older IDs working during a migration.
+pub fn legacy_device_id(id: &str) -> Option<String> {
+    match device_id_version(id) {
+        Some(1) => legacy_machine_device_id(id),
+        _ => None,
+    }
+}
+
+#[cfg(all(feature = "fingerprint", not(target_arch = "wasm32")))]
+fn legacy_machine_device_id(id: &str) -> Option<String> {
+    // Only this machine's own ID has a known predecessor.
+    if *CACHED_DEVICE_ID.read().unwrap_or_else(|e| e.into_inner()) != id {
+        return None;
+    }
+
+    Some(legacy_hash(
+        &system::hostname().unwrap_or_default(),
+        &system::os_type().unwrap_or_default(),
+        &system::os_release().unwrap_or_default(),
+    ))
+}
+
+#[cfg(not(all(feature = "fingerprint", not(target_arch = "wasm32"))))]
+fn legacy_machine_device_id(_id: &str) -> Option<String> {
+    None
+}
 
-    // Take first 32 characters of hex hash
-    let result = hasher.finalize();
-    hex::encode(result)[..32].to_string()
+/// The version 0 device ID for the given system identifiers.
+#[cfg_attr(not(all(feature = "fingerprint", not(target_arch = "wasm32"))), allow(dead_code))]
+fn legacy_hash(hostname: &str, os_type: &str, os_release: &str) -> String {
+    hash_string(&format!("{}-{}-{}", hostname, os_type, os_release))[..32].to_string()
+}
+
+/// Whether two device IDs identify the same device.
+///
+/// IDs match if they are equal, or if one is this machine's version 1 ID
+/// and the other its version 0 ID (see [`legacy_device_id`]).
+pub fn device_ids_match(a: &str, b: &str) -> bool {
+    a == b || legacy_device_id(a).as_deref() == Some(b) || legacy_device_id(b).as_deref() == Some(a)
+}
+
+fn is_device_hash(value: &str) -> bool {
+    value.len() == 32 && value.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Whether a hex string has the version and variant bits of a random UUID.
+fn is_random_uuid(value: &str) -> bool {
+    uuid::Uuid::try_parse(value)
+        .map(|uuid| uuid.get_version() == Some(uuid::Version::Random) && uuid.get_variant() == uuid::Variant::RFC4122)
+        .unwrap_or(false)
+}
+
+/// Read the MAC address of the first non-loopback interface, by name.
+#[cfg(all(feature = "fingerprint", target_os = "linux"))]
+fn read_mac_address() -> Option<String> {
+    let mut interfaces: Vec<_> = fs::read_dir("/sys/class/net").ok()?.flatten().collect();
+    interfaces.sort_by_key(|entry| entry.file_name());
+
+    interfaces
+        .iter()
+        .filter(|entry| entry.file_name() != "lo")
+        .filter_map(|entry| fs::read_to_string(entry.path().join("address")).ok())
+        .map(|address| address.trim().to_lowercase())
+        .find(|address| !address.is_empty() && address != "00:00:00:00:00:00")
+}
+
+#[cfg(all(feature = "fingerprint", not(target_os = "linux")))]
+fn read_mac_address() -> Option<String> {
+    None
+}
+
+/// Get the machine identifier assigned by the operating system.
+///
+/// Reads `/etc/machine-id` on Linux, the `MachineGuid` registry value on
+/// Windows and `IOPlatformUUID` on macOS. These stay the same across reboots
+/// but differ between freshly imaged machines. Returns `None` on other
+/// platforms or if the identifier can't be read.
+#[cfg(feature = "fingerprint")]
+pub fn get_machine_id() -> Option<String> {
+    read_machine_id()
+        .map(|id| id.trim().to_lowercase())
+        .filter(|id| !id.is_empty())
+}
+
+#[cfg(all(feature = "fingerprint", target_os = "linux"))]
+fn read_machine_id() -> Option<String> {
+    ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+}
+
+#[cfg(all(feature = "fingerprint", target_os = "macos"))]
+fn read_machine_id() -> Option<String> {
+    let output = Command::new("ioreg")
+        .args(["-rd1", "-c", "IOPlatformExpertDevice"])
+        .output()
+        .ok()?;
+
+    // Line format: "IOPlatformUUID" = "XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX"
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|line| line.contains("IOPlatformUUID"))
+        .and_then(|line| line.split('"').nth(3))
+        .map(str::to_string)
+}
+
+#[cfg(all(feature = "fingerprint", target_os = "windows"))]
+fn read_machine_id() -> Option<String> {
+    let output = Command::new("reg")
+        .args(["query", r"HKLM\SOFTWARE\Microsoft\Cryptography", "/v", "MachineGuid"])
+        .output()
+        .ok()?;
+
+    // Line format: MachineGuid    REG_SZ    xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|line| line.contains("MachineGuid"))
+        .and_then(|line| line.split_whitespace().last())
+        .map(str::to_string)
+}
+
+#[cfg(all(
+    feature = "fingerprint",
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows"))
+))]
+fn read_machine_id() -> Option<String> {
+    None
+}
+
+/// Substrings of hardware vendor and product names reported by hypervisors.
+#[cfg(feature = "fingerprint")]
+const VIRTUAL_MACHINE_VENDORS: &[&str] = &[
+    "vmware",
+    "virtualbox",
+    "innotek",
+    "qemu",
+    "kvm",
+    "xen",
+    "parallels",
+    "bochs",
+    "bhyve",
+    "virtual machine",
+    "amazon ec2",
+    "google compute engine",
+];
+
+/// Cached result of the virtual machine checks, which may spawn processes.
+#[cfg(feature = "fingerprint")]
+static IS_VIRTUAL_MACHINE: Lazy<bool> =
+    Lazy::new(|| has_hypervisor_cpu_flag() || read_hardware_vendors().iter().any(|v| is_virtual_vendor(v)));
+
+/// Check whether this device appears to be running inside a virtual machine.
+///
+/// Best effort: looks at the DMI vendor and product strings on Linux, the BIOS
+/// `SystemManufacturer` and `SystemProductName` on Windows, `kern.hv_vmm_present`
+/// on macOS and the CPUID hypervisor bit on x86 (except on Windows, where
+/// Hyper-V sets it on physical hosts too). Returns `false` when none of the
+/// signals can be read. The result is computed once per process.
+#[cfg(feature = "fingerprint")]
+pub fn is_virtual_machine() -> bool {
+    *IS_VIRTUAL_MACHINE
+}
+
+#[cfg(feature = "fingerprint")]
+fn is_virtual_vendor(value: &str) -> bool {
+    let value = value.to_lowercase();
+    VIRTUAL_MACHINE_VENDORS.iter().any(|vendor| value.contains(vendor))
+}
+
+#[cfg(all(
+    feature = "fingerprint",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(target_os = "windows"),
+    not(target_env = "sgx")
+))]
+#[allow(unused_unsafe)]
+fn has_hypervisor_cpu_flag() -> bool {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::__cpuid;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::__cpuid;
+
+    // SAFETY: CPUID is available on every x86 CPU Rust targets outside SGX.
+    let features = unsafe { __cpuid(1) };
+    features.ecx & (1 << 31) != 0
+}
+
+#[cfg(all(
+    feature = "fingerprint",
+    not(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_os = "windows"), not(target_env = "sgx")))
+))]
+fn has_hypervisor_cpu_flag() -> bool {
+    false
+}
+
+#[cfg(all(feature = "fingerprint", target_os = "linux"))]
+fn read_hardware_vendors() -> Vec<String> {
+    ["sys_vendor", "product_name", "board_vendor", "bios_vendor"]
+        .iter()
+        .filter_map(|name| fs::read_to_string(Path::new("/sys/class/dmi/id").join(name)).ok())
+        .collect()
+}
+
+#[cfg(all(feature = "fingerprint", target_os = "macos"))]
+fn read_hardware_vendors() -> Vec<String> {
+    let present = Command::new("sysctl")
+        .args(["-n", "kern.hv_vmm_present"])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "1")
+        .unwrap_or(false);
+
+    // Reported as a vendor so that the caller's vendor check matches.
+    if present {
+        vec!["virtual machine".to_string()]
+    } else {
+        Vec::new()
+    }
+}
+
+#[cfg(all(feature = "fingerprint", target_os = "windows"))]
+fn read_hardware_vendors() -> Vec<String> {
+    ["SystemManufacturer", "SystemProductName"]
+        .iter()
+        .filter_map(|name| {
+            let output = Command::new("reg")
+                .args(["query", r"HKLM\HARDWARE\DESCRIPTION\System\BIOS", "/v", name])
+                .output()
+                .ok()?;
+
+            // Line format: SystemManufacturer    REG_SZ    VMware, Inc.
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .find(|line| line.contains(name))
+                .and_then(|line| line.split("REG_SZ").nth(1))
+                .map(|value| value.trim().to_string())
+        })
+        .collect()
+}
+
+#[cfg(all(
+    feature = "fingerprint",
+    not(any(target_os = "linux", target_os = "macos", target_os = "windows"))
+))]
+fn read_hardware_vendors() -> Vec<String> {
+    Vec::new()
 }
 
 /// Get the current device information.
 ///
 /// This function gathers platform, architecture, and other system information
 /// to create a complete device profile for license activation.
+#[cfg(feature = "fingerprint")]
 pub fn get_device_info() -> DeviceInfo {
+    get_device_info_for(get_device_id())
+}
+
+/// Get the current device information for a device ID chosen by the caller.
+///
+/// Same as [`get_device_info`], but doesn't derive a device ID from the
+/// machine, so it is also available without the `fingerprint` feature.
+pub fn get_device_info_for(device_id: impl Into<String>) -> DeviceInfo {
     DeviceInfo {
-        device_id: get_device_id(),
+        device_id: device_id.into(),
         device_name: get_device_name(),
         platform: get_platform(),
         platform_version: get_platform_version(),
-        architecture: get_architecture(),
+        architecture: Architecture::from_target(),
+        #[cfg(feature = "fingerprint")]
+        is_virtual: is_virtual_machine(),
+        #[cfg(not(feature = "fingerprint"))]
+        is_virtual: false,
     }
 }
 
 /// Detect the current platform.
+///
+/// iOS and Android (e.g. Tauri mobile apps) are recognized from the build
+/// target, since the kernel reports Android as Linux and iOS as Darwin. Other
+/// platforms are detected at runtime.
 pub fn get_platform() -> Platform {
-    let os_type = sys_info::os_type().unwrap_or_default().to_lowercase();
+    if cfg!(target_os = "ios") {
+        return Platform::Ios;
+    }
+    if cfg!(target_os = "android") {
+        return Platform::Android;
+    }
+
+    let os_type = system::os_type().unwrap_or_default().to_lowercase();
 
     if os_type.contains("windows") {
         Platform::Windows
@@ -129,7 +801,7 @@ pub fn get_platform() -> Platform {
         Platform::Macos
     } else if os_type.contains("linux") {
         // Check for Android via os_release
-        let os_release = sys_info::os_release().unwrap_or_default().to_lowercase();
+        let os_release = system::os_release().unwrap_or_default().to_lowercase();
         if os_release.contains("android") {
             Platform::Android
         } else {
@@ -144,53 +816,199 @@ pub fn get_platform() -> Platform {
     }
 }
 
-/// Get a human-readable device name.
+/// Get a human-readable device name, safe to send to the server.
+///
+/// The name is derived from the hostname and passed through
+/// [`sanitize_device_name`]. If nothing is left, e.g. for a hostname written
+/// only in CJK characters, a generated name is used instead. See
+/// [`get_raw_device_name`] for the name as the system reports it.
 pub fn get_device_name() -> String {
-    // Try hostname first
-    if let Ok(hostname) = sys_info::hostname() {
-        if !hostname.is_empty() {
-            // On Windows, prepend username if available
-            #[cfg(target_os = "windows")]
-            {
-                if let Ok(info) = sys_info::info() {
-                    let username = info.username.unwrap_or_default();
-                    if !username.is_empty() && username != "Unknown" {
-                        return format!("{}-{}", username, hostname);
-                    }
-                }
+    system_device_name()
+        .map(|name| sanitize_device_name(&name))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(fallback_device_name)
+}
+
+/// Get the device name as the system reports it, normalized to NFC.
+///
+/// May contain any Unicode characters; pass it to
+/// `ActivateOptions::with_device_name` if the server accepts them.
+pub fn get_raw_device_name() -> String {
+    system_device_name()
+        .map(|name| name.nfc().collect())
+        .unwrap_or_else(fallback_device_name)
+}
+
+/// Reduce a device name to characters every server accepts.
+///
+/// Accents are removed (`José` becomes `Jose`), a few letters without a
+/// decomposition are transliterated (`ß` becomes `ss`), and anything else
+/// outside ASCII letters, digits, spaces and `-_.'` is dropped. Whitespace
+/// runs are collapsed and separators are trimmed from the ends, so the result
+/// may be empty.
+pub fn sanitize_device_name(raw: &str) -> String {
+    let mut safe = String::with_capacity(raw.len());
+    for c in raw.nfd().filter(|c| !is_combining_mark(*c)) {
+        match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' | '\'' => safe.push(c),
+            c if c.is_whitespace() => safe.push(' '),
+            c => safe.push_str(transliterate(c)),
+        }
+    }
+
+    safe.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c| matches!(c, '-' | '_' | '.'))
+        .to_string()
+}
+
+/// ASCII spelling of letters that don't decompose into a base letter and
+/// accents, or `""` for characters to drop.
+fn transliterate(c: char) -> &'static str {
+    match c {
+        'ß' => "ss",
+        'æ' => "ae",
+        'Æ' => "AE",
+        'œ' => "oe",
+        'Œ' => "OE",
+        'ø' => "o",
+        'Ø' => "O",
+        'ł' => "l",
+        'Ł' => "L",
+        'đ' | 'ð' => "d",
+        'Đ' | 'Ð' => "D",
+        'þ' => "th",
+        'Þ' => "Th",
+        '\u{2018}' | '\u{2019}' => "'",
+        '\u{2010}'..='\u{2015}' => "-",
+        _ => "",
+    }
+}
+
+/// Device name from the hostname, prefixed with the user name on Windows.
+fn system_device_name() -> Option<String> {
+    let hostname = system::hostname().filter(|hostname| !hostname.is_empty())?;
+
+    #[cfg(all(feature = "fingerprint", target_os = "windows"))]
+    {
+        if let Ok(info) = sys_info::info() {
+            let username = info.username.unwrap_or_default();
+            if !username.is_empty() && username != "Unknown" {
+                return Some(format!("{}-{}", username, hostname));
             }
-            return hostname;
         }
     }
 
-    // Fallback to a generated name based on device ID
-    let device_id = get_device_id();
-    format!("device-{}", &device_id[..8])
+    Some(hostname)
+}
+
+/// Name generated from the device ID, for when there is no hostname.
+#[cfg(feature = "fingerprint")]
+fn fallback_device_name() -> String {
+    device_name_from_id(&get_device_id())
+}
+
+/// `device-` followed by the start of `device_id`, which may be shorter than
+/// 8 characters or not ASCII when set through `LYCENTO_DEVICE_ID`.
+#[cfg(feature = "fingerprint")]
+fn device_name_from_id(device_id: &str) -> String {
+    format!("device-{}", device_id.chars().take(8).collect::<String>())
+}
+
+/// Name based on the platform, since there is no device ID to derive one from.
+#[cfg(not(feature = "fingerprint"))]
+fn fallback_device_name() -> String {
+    format!("{} device", get_platform())
+}
+
+/// Device ID used when a persistent one can't be stored.
+#[cfg(feature = "fingerprint")]
+fn fallback_device_id() -> String {
+    generate_device_id()
+}
+
+/// Device ID used when a persistent one can't be stored.
+#[cfg(not(feature = "fingerprint"))]
+fn fallback_device_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
 }
 
 /// Get the platform version/release.
 pub fn get_platform_version() -> String {
-    sys_info::os_release().unwrap_or_else(|_| "unknown".to_string())
+    system::os_release().unwrap_or_else(|| "unknown".to_string())
+}
+
+/// System information from `sys_info`, which doesn't build for WASM.
+#[cfg(all(feature = "fingerprint", not(target_arch = "wasm32")))]
+mod system {
+    pub(super) fn hostname() -> Option<String> {
+        sys_info::hostname().ok()
+    }
+
+    pub(super) fn os_type() -> Option<String> {
+        sys_info::os_type().ok()
+    }
+
+    pub(super) fn os_release() -> Option<String> {
+        sys_info::os_release().ok()
+    }
+}
+
+/// System information is not available on WASM or without the `fingerprint`
+/// feature. The OS type is taken from the build target instead.
+#[cfg(any(not(feature = "fingerprint"), target_arch = "wasm32"))]
+mod system {
+    pub(super) fn hostname() -> Option<String> {
+        None
+    }
+
+    pub(super) fn os_type() -> Option<String> {
+        Some(std::env::consts::OS.to_string())
+    }
+
+    pub(super) fn os_release() -> Option<String> {
+        None
+    }
+}
+
+/// Get the primary local IP address of this machine.
+///
+/// This is the LAN address of the interface used for outbound traffic
+/// (e.g. `192.168.1.20`), not the public IP seen by the license server.
+/// IPv4 is preferred over IPv6. No packets are sent: the address is read
+/// from a UDP socket connected to a public resolver. Returns `None` if the
+/// machine has no usable interface.
+pub fn get_local_ip() -> Option<String> {
+    let targets: [(&str, SocketAddr); 2] = [
+        ("0.0.0.0:0", SocketAddr::from(([8, 8, 8, 8], 80))),
+        ("[::]:0", SocketAddr::from(([0x2001, 0x4860, 0x4860, 0, 0, 0, 0, 0x8888], 80))),
+    ];
+
+    select_local_ip(targets.iter().map(|(bind, target)| local_ip_towards(bind, *target)))
+}
+
+/// The first usable address among `candidates`, in order of preference.
+fn select_local_ip(candidates: impl IntoIterator<Item = Option<IpAddr>>) -> Option<String> {
+    candidates
+        .into_iter()
+        .flatten()
+        .find(|ip| !ip.is_loopback() && !ip.is_unspecified())
+        .map(|ip| ip.to_string())
+}
+
+/// Local address the OS would use to reach `target`.
+fn local_ip_towards(bind: &str, target: SocketAddr) -> Option<IpAddr> {
+    let socket = UdpSocket::bind(bind).ok()?;
+    socket.connect(target).ok()?;
+    socket.local_addr().ok().map(|addr| addr.ip())
 }
 
 /// Get the system architecture.
+///
+/// Kept for backward compatibility; prefer [`Architecture::from_target`].
 pub fn get_architecture() -> String {
-    // sys_info doesn't have arch, so we detect it from the OS type
-    #[cfg(target_arch = "x86_64")]
-    return "x86_64".to_string();
-    #[cfg(target_arch = "aarch64")]
-    return "aarch64".to_string();
-    #[cfg(target_arch = "x86")]
-    return "x86".to_string();
-    #[cfg(target_arch = "arm")]
-    return "arm".to_string();
-    #[cfg(not(any(
-        target_arch = "x86_64",
-        target_arch = "aarch64",
-        target_arch = "x86",
-        target_arch = "arm"
-    )))]
-    return "unknown".to_string();
+    Architecture::from_target().as_str().to_string()
 }
 
 /// Hash a string using SHA256.
@@ -212,6 +1030,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
     fn test_platform_detection() {
         let platform = get_platform();
         assert!(matches!(
@@ -221,22 +1040,271 @@ mod tests {
     }
 
     #[test]
+    #[cfg(target_os = "ios")]
+    fn test_platform_detection_ios() {
+        assert_eq!(get_platform(), Platform::Ios);
+    }
+
+    #[test]
+    #[cfg(target_os = "android")]
+    fn test_platform_detection_android() {
+        assert_eq!(get_platform(), Platform::Android);
+    }
+
+    #[test]
+    fn test_fingerprint_is_sorted_and_ignores_device_id() {
+        let info = DeviceInfo {
+            device_id: "device-1".to_string(),
+            device_name: "Workstation".to_string(),
+            platform: Platform::Linux,
+            platform_version: "6.1".to_string(),
+            architecture: Architecture::X86_64,
+            is_virtual: false,
+        };
+        let json = info.fingerprint_json();
+        assert!(json.starts_with(r#"{"architecture":"#), "{}", json);
+        assert!(!json.contains("device-1"));
+
+        let other = DeviceInfo {
+            device_id: "device-2".to_string(),
+            ..info.clone()
+        };
+        assert_eq!(other.fingerprint_hash(), info.fingerprint_hash());
+        assert_eq!(info.fingerprint_hash().len(), 64);
+    }
+
+    #[test]
+    fn test_display() {
+        let info = DeviceInfo {
+            device_id: "device-1".to_string(),
+            device_name: "Workstation".to_string(),
+            platform: Platform::Linux,
+            platform_version: "6.1".to_string(),
+            architecture: Architecture::X86_64,
+            is_virtual: false,
+        };
+        assert_eq!(
+            info.to_string(),
+            "Workstation\n  Platform: Linux 6.1 (x86_64)\n  Device ID: device-1\n  Virtual machine: no"
+        );
+        assert_eq!(Platform::Macos.to_string(), "macOS");
+        assert_eq!(Platform::Macos.as_str(), "macos");
+    }
+
+    #[test]
+    #[cfg(feature = "fingerprint")]
+    fn test_virtual_machine_vendors() {
+        assert!(is_virtual_vendor("VMware, Inc.\n"));
+        assert!(is_virtual_vendor("innotek GmbH"));
+        assert!(is_virtual_vendor("QEMU"));
+        assert!(is_virtual_vendor("Virtual Machine"));
+        assert!(!is_virtual_vendor("Dell Inc."));
+        assert!(!is_virtual_vendor("Microsoft Corporation"));
+        assert_eq!(is_virtual_machine(), is_virtual_machine());
+    }
+
+    #[test]
+    #[cfg(feature = "fingerprint")]
     fn test_device_id_consistency() {
         let id1 = get_device_id();
         let id2 = get_device_id();
         assert_eq!(id1, id2);
+
+        assert_eq!(refresh_device_id(), generate_device_id());
+        assert_eq!(get_device_id(), id1);
+    }
+
+    #[test]
+    fn test_device_id_from_env() {
+        // The environment isn't modified, as tests run in parallel.
+        assert_eq!(device_id_from_env("LYCENTO_DEVICE_ID_TEST_UNSET"), None);
+        assert_eq!(
+            non_empty_device_id(Some(" ci-runner-1 ".to_string())).as_deref(),
+            Some(" ci-runner-1 ")
+        );
+        assert_eq!(non_empty_device_id(Some(String::new())), None);
+    }
+
+    #[test]
+    #[cfg(feature = "fingerprint")]
+    fn test_device_name_from_id() {
+        assert_eq!(device_name_from_id("v1:0123456789abcdef"), "device-v1:01234");
+        assert_eq!(device_name_from_id("ci"), "device-ci");
+        assert_eq!(device_name_from_id("日本語のテスト端末です"), "device-日本語のテスト端");
+    }
+
+    #[test]
+    #[cfg(feature = "fingerprint")]
+    fn test_generated_device_id_is_deterministic() {
+        assert_eq!(get_machine_id(), get_machine_id());
+
+        let id = generate_device_id();
+        assert_eq!(id.len(), 35);
+        assert_eq!(device_id_version(&id), Some(DEVICE_ID_VERSION));
+        assert_eq!(id, generate_device_id());
+    }
+
+    #[test]
+    #[cfg(feature = "fingerprint")]
+    fn test_device_id_builder() {
+        // The default must keep producing the version 1 IDs existing activations use.
+        let components = [
+            system::hostname().unwrap_or_default(),
+            system::os_type().unwrap_or_default(),
+            system::os_release().unwrap_or_default(),
+        ]
+        .into_iter()
+        .chain(get_machine_id())
+        .collect::<Vec<_>>()
+        .join("-");
+        let id = DeviceIdBuilder::default().build();
+        assert_eq!(id, format!("v1:{}", &hash_string(&components)[..32]));
+
+        let salted = DeviceIdBuilder::default().with_salt("app-a").build();
+        assert_eq!(salted.len(), 35);
+        assert_ne!(salted, DeviceIdBuilder::default().with_salt("app-b").build());
+        assert_ne!(
+            DeviceIdBuilder::default().with_hostname(false).build(),
+            generate_device_id()
+        );
     }
 
     #[test]
+    #[cfg(feature = "fingerprint")]
+    fn test_device_id_builder_without_components() {
+        let nothing = DeviceIdBuilder::default()
+            .with_hostname(false)
+            .with_os_type(false)
+            .with_os_release(false)
+            .with_machine_id(false);
+        let only_machine = match get_machine_id() {
+            Some(_) => nothing.clone().with_machine_id(true),
+            None => nothing.clone().with_hostname(true),
+        };
+
+        assert_eq!(nothing.build(), only_machine.build());
+        assert_ne!(nothing.build(), format!("v1:{}", &hash_string("")[..32]));
+        assert_eq!(
+            nothing.clone().with_salt("app").build(),
+            only_machine.with_salt("app").build()
+        );
+    }
+
+    #[test]
+    fn test_device_id_version() {
+        let hash = "0123456789abcdef0123456789abcdef";
+        let v1 = format!("v1:{}", hash);
+
+        assert_eq!(device_id_version(&v1), Some(1));
+        assert_eq!(device_id_version(hash), Some(0));
+        assert_eq!(device_id_version("v2:0123"), None);
+        assert_eq!(device_id_version("my-custom-id"), None);
+
+        // Random IDs stored by get_persistent_device_id aren't version 0 hashes
+        let persistent = uuid::Uuid::new_v4().simple().to_string();
+        assert_eq!(device_id_version(&persistent), None);
+        assert_eq!(device_id_version(&persistent.to_uppercase()), None);
+
+        assert_eq!(legacy_device_id(hash), None);
+        assert!(!device_ids_match(&v1, hash));
+        assert!(!device_ids_match(&v1, "my-custom-id"));
+    }
+
+    #[test]
+    fn test_legacy_hash_matches_earlier_releases() {
+        // Generated by the last release before version 1 for these identifiers
+        assert_eq!(legacy_hash("workstation", "Linux", "6.1.0-13-amd64"), "8330032c39b90e71b2548811f0f40f07");
+    }
+
+    #[test]
+    #[cfg(all(feature = "fingerprint", not(target_arch = "wasm32")))]
+    fn test_legacy_device_id_is_recomputed() {
+        let legacy = legacy_device_id(&generate_device_id()).unwrap();
+        assert_eq!(
+            legacy,
+            legacy_hash(
+                &system::hostname().unwrap_or_default(),
+                &system::os_type().unwrap_or_default(),
+                &system::os_release().unwrap_or_default(),
+            )
+        );
+        assert!(device_ids_match(&generate_device_id(), &legacy));
+
+        // Another machine's version 1 ID has no known predecessor.
+        let other = "v1:0123456789abcdef0123456789abcdef";
+        assert_eq!(legacy_device_id(other), None);
+        assert!(!device_ids_match(&legacy, other));
+    }
+
+    #[test]
+    fn test_local_ip_selection() {
+        let lan: IpAddr = "192.168.1.20".parse().unwrap();
+        let v6: IpAddr = "2001:db8::20".parse().unwrap();
+        let loopback: IpAddr = "127.0.0.1".parse().unwrap();
+        let unspecified: IpAddr = "0.0.0.0".parse().unwrap();
+
+        assert_eq!(select_local_ip([Some(lan), Some(v6)]).as_deref(), Some("192.168.1.20"));
+        assert_eq!(select_local_ip([None, Some(v6)]).as_deref(), Some("2001:db8::20"));
+        assert_eq!(select_local_ip([Some(loopback), Some(v6)]).as_deref(), Some("2001:db8::20"));
+        assert_eq!(select_local_ip([Some(unspecified), Some(IpAddr::from([0u16; 8]))]), None);
+        assert_eq!(select_local_ip([None, None]), None);
+    }
+
+    #[test]
+    fn test_architecture_serializes_lowercase() {
+        assert_eq!(serde_json::to_string(&Architecture::Aarch64).unwrap(), r#""aarch64""#);
+        assert_eq!(serde_json::to_string(&Architecture::X86_64).unwrap(), r#""x86_64""#);
+        assert_eq!(get_architecture(), Architecture::from_target().as_str());
+    }
+
+    #[test]
+    #[cfg(feature = "fingerprint")]
     fn test_device_info() {
         let info = get_device_info();
         assert!(!info.device_id.is_empty());
         assert!(!info.device_name.is_empty());
     }
 
+    #[test]
+    fn test_device_info_for() {
+        let info = get_device_info_for("device-1");
+        assert_eq!(info.device_id, "device-1");
+        assert!(!info.device_name.is_empty());
+    }
+
+    #[test]
+    fn test_persistent_device_id_is_reused() {
+        let path = std::env::temp_dir()
+            .join(format!("lycento-sdk-test-{}", uuid::Uuid::new_v4().simple()))
+            .join(DEVICE_ID_FILE);
+
+        let (first, created) = load_or_create_device_id(&path).unwrap();
+        let (second, created_again) = load_or_create_device_id(&path).unwrap();
+
+        assert!(created);
+        assert!(!created_again);
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 32);
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
     #[test]
     fn test_hash_string() {
         let hash = hash_string("test-input");
         assert_eq!(hash.len(), 64);
     }
+
+    #[test]
+    fn test_sanitize_device_name() {
+        assert_eq!(sanitize_device_name("DESKTOP-4F2K"), "DESKTOP-4F2K");
+        // Precomposed and decomposed accents give the same result
+        assert_eq!(sanitize_device_name("Jos\u{e9}-PC"), "Jose-PC");
+        assert_eq!(sanitize_device_name("Jose\u{301}-PC"), "Jose-PC");
+        assert_eq!(sanitize_device_name("Straße  Büro\t1"), "Strasse Buro 1");
+        assert_eq!(sanitize_device_name("Zoë\u{2019}s MacBook"), "Zoe's MacBook");
+        assert_eq!(sanitize_device_name("田中-ThinkPad"), "ThinkPad");
+        assert_eq!(sanitize_device_name("開発機"), "");
+        assert_eq!(sanitize_device_name("pc\u{200b}\u{0}name"), "pcname");
+    }
 }
@@ -0,0 +1,95 @@
+//! Dry-run mode, enabled with [`LycentoConfig::with_dry_run`](crate::LycentoConfig::with_dry_run).
+//!
+//! Requests are logged instead of sent, and answered with a canned successful
+//! response for their endpoint. The canned responses echo the license key and
+//! device ID of the request, so they parse into the usual response types.
+//! Responses set with
+//! [`LycentoConfig::with_dry_run_response`](crate::LycentoConfig::with_dry_run_response)
+//! take precedence.
+
+use crate::logging::redact_json;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Device ID used in canned responses to requests that don't name one.
+const DRY_RUN_DEVICE_ID: &str = "dry-run-device";
+
+/// Fixed timestamp used in canned responses, so runs can be diffed.
+const DRY_RUN_TIMESTAMP: &str = "2000-01-01T00:00:00Z";
+
+/// Answers requests in dry-run mode.
+#[derive(Debug, Default)]
+pub(crate) struct DryRun {
+    responses: HashMap<String, Value>,
+}
+
+impl DryRun {
+    pub(crate) fn new(responses: HashMap<String, Value>) -> Self {
+        Self { responses }
+    }
+
+    /// Log the request that would have been sent to `url` and return the
+    /// response for `path`.
+    pub(crate) fn respond(&self, method: &str, url: &str, path: &str, request: &Value, redact: bool) -> Value {
+        if redact {
+            log::info!("Dry run, not sending {} {} {}", method, url, redact_json(request));
+        } else {
+            log::info!("Dry run, not sending {} {} {}", method, url, request);
+        }
+
+        match self.responses.get(path) {
+            Some(response) => response.clone(),
+            None => canned_response(path, request),
+        }
+    }
+}
+
+/// Request parameters of a GET request, as logged in dry-run mode.
+pub(crate) fn query_json(license_key: &str, query: &[(&str, &str)]) -> Value {
+    let mut request = json!({ "license_key": license_key });
+    for (name, value) in query {
+        request[*name] = json!(value);
+    }
+    request
+}
+
+/// Successful response of the server for `path`, filled in from `request`.
+fn canned_response(path: &str, request: &Value) -> Value {
+    let text = |field: &str| request.get(field).and_then(Value::as_str);
+    let license_key = text("license_key").unwrap_or_default();
+    let device_id = text("device_id").unwrap_or(DRY_RUN_DEVICE_ID);
+
+    let license = json!({
+        "key": license_key,
+        "status": "active",
+        "type": "perpetual",
+        "expiresAt": null,
+        "maxDevices": 1,
+        "activeDevices": 1,
+    });
+    let activation = json!({
+        "id": 1,
+        "deviceId": device_id,
+        "deviceName": text("device_name").unwrap_or(DRY_RUN_DEVICE_ID),
+        "devicePlatform": text("device_platform").unwrap_or(std::env::consts::OS),
+        "activatedAt": DRY_RUN_TIMESTAMP,
+    });
+    let mut record = activation.clone();
+    record["deactivatedAt"] = Value::Null;
+    record["isActive"] = json!(true);
+
+    match path {
+        "/licenses/validate" => json!({ "valid": true, "license": license, "activation": activation }),
+        "/licenses/activate" => json!({ "success": true, "license": license, "activation": activation }),
+        "/licenses/deactivate" => json!({
+            "success": true,
+            "message": "Dry run",
+            "activation": { "id": 1, "deviceId": device_id, "deactivatedAt": DRY_RUN_TIMESTAMP },
+        }),
+        "/licenses/renew" => json!({ "success": true, "expiresAt": null, "license": license }),
+        "/licenses/check" => json!({ "canActivate": true, "remainingSlots": 1, "alreadyActivated": false }),
+        "/licenses/info" => json!({ "license": license, "activations": [record] }),
+        "/licenses/activations" => json!({ "activations": [record], "page": 1, "perPage": 1, "total": 1 }),
+        _ => json!({ "success": true }),
+    }
+}
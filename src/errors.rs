@@ -6,14 +6,208 @@
 //! - `ValidationError` - License validation failures
 //! - `DeactivationError` - License deactivation failures
 //! - `NetworkError` - Network connectivity issues
+//!
+//! Every error also has a machine-readable [`ErrorCode`] through `code()`,
+//! for mapping errors to localized messages.
 
+use crate::retry::is_connect;
+use std::error::Error as StdError;
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
 
+/// Boxed error kept as the `source()` of a wrapping error.
+pub type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Stand-in for an error source that couldn't be copied, keeping its message.
+#[derive(Debug, Error)]
+#[error("{0}")]
+struct SourceMessage(String);
+
+/// Message for rate limited requests.
+const RATE_LIMITED: &str = "Rate limit exceeded - please try again later";
+
+/// Message for responses whose `X-Signature` doesn't match their body.
+const SIGNATURE_MISMATCH: &str = "Response signature verification failed";
+
+/// Message for offline licenses past their expiry.
+const LICENSE_EXPIRED: &str = "License has expired";
+
+/// Message for responses over the configured size limit.
+const RESPONSE_TOO_LARGE: &str = "Response from server is too large";
+
+/// Message for activations cancelled by the caller.
+const CANCELLED: &str = "Activation was cancelled";
+
+/// Message for operations that didn't complete in time.
+const TIMED_OUT: &str = "Timed out waiting for the license server";
+
+/// Message for errors caused by a response body that failed to parse.
+const UNEXPECTED_RESPONSE: &str = "Unexpected response format from server";
+
+/// Machine-readable category of an error, e.g. for localized messages.
+///
+/// Derived from the error's type and the HTTP status where the server sent
+/// one. Only errors the server reported with a message alone, such as
+/// `ActivationError::Custom`, are classified by that message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// The license or activation doesn't exist.
+    NotFound,
+    /// Too many requests; retry later.
+    RateLimited,
+    /// The license has expired.
+    Expired,
+    /// The license has no free device slots.
+    DeviceLimit,
+    /// The server couldn't be reached or didn't respond in time.
+    Network,
+    /// The API key is missing, invalid or not allowed to make the request.
+    Auth,
+    /// The caller cancelled the operation.
+    Cancelled,
+    /// Any other failure.
+    Unknown,
+}
+
+impl ErrorCode {
+    /// The code as a stable `snake_case` string, e.g. `"device_limit"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::RateLimited => "rate_limited",
+            Self::Expired => "expired",
+            Self::DeviceLimit => "device_limit",
+            Self::Network => "network",
+            Self::Auth => "auth",
+            Self::Cancelled => "cancelled",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// Classify an HTTP error by its status, falling back to its message.
+    fn from_status(status: u16, message: &str) -> Self {
+        match status {
+            401 | 403 => Self::Auth,
+            404 => Self::NotFound,
+            429 => Self::RateLimited,
+            _ => Self::from_message(message),
+        }
+    }
+
+    /// Classify an error message or reason code sent by the server.
+    fn from_message(message: &str) -> Self {
+        let message = message.to_lowercase().replace('_', " ");
+        let mentions = |phrases: &[&str]| phrases.iter().any(|phrase| message.contains(phrase));
+
+        if mentions(&["rate limit", "too many requests"]) {
+            Self::RateLimited
+        } else if mentions(&["device limit", "maximum number of devices", "max devices", "too many devices"]) {
+            Self::DeviceLimit
+        } else if mentions(&["expired"]) {
+            Self::Expired
+        } else if mentions(&["not found"]) {
+            Self::NotFound
+        } else if mentions(&["unauthorized", "forbidden", "api key"]) {
+            Self::Auth
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Base error type for all Lycento SDK errors.
 #[derive(Debug, Error)]
 pub enum LycentoError {
     #[error("LycentoError: {0}")]
     Custom(String),
+    /// The client configuration is invalid.
+    #[error("LycentoError: invalid configuration - {0}")]
+    InvalidConfig(String),
+    /// A failure from an underlying library, available through `source()`.
+    #[error("LycentoError: {message}")]
+    Wrapped {
+        message: String,
+        #[source]
+        source: BoxError,
+    },
+    /// The server responded with a non-success HTTP status.
+    #[error("LycentoError: HTTP {status} - {message}")]
+    Http {
+        status: u16,
+        message: String,
+        /// Machine-readable error code from the `code` field of the response
+        /// body, e.g. `"activation_not_found"`, if the server sent one.
+        reason: Option<String>,
+    },
+    /// The server could not be reached.
+    #[error(transparent)]
+    Network(#[from] NetworkError),
+    /// An activation failure propagated as the base error type.
+    #[error(transparent)]
+    Activation(ActivationError),
+    /// A validation failure propagated as the base error type.
+    #[error(transparent)]
+    Validation(ValidationError),
+    /// A deactivation failure propagated as the base error type.
+    #[error(transparent)]
+    Deactivation(DeactivationError),
+    /// The server rejected the API key with 401 Unauthorized.
+    #[error("LycentoError: {message}")]
+    Unauthorized { message: String },
+    /// The server refused the request with 403 Forbidden, e.g. because the
+    /// API key lacks permission.
+    #[error("LycentoError: {message}")]
+    Forbidden { message: String },
+    /// The server rejected the request with 429 Too Many Requests.
+    #[error("LycentoError: {RATE_LIMITED}")]
+    RateLimited {
+        /// How long the server asked to wait, from the `Retry-After` header.
+        retry_after: Option<Duration>,
+    },
+    /// Activating the new device of a transfer failed.
+    #[error("LycentoError: transfer failed - {source}")]
+    TransferFailed {
+        /// Why the new device couldn't be activated.
+        #[source]
+        source: ActivationError,
+        /// Whether the old device was re-activated.
+        rolled_back: bool,
+    },
+    /// The response body exceeded the configured maximum size.
+    #[error("LycentoError: {RESPONSE_TOO_LARGE} (limit {limit} bytes)")]
+    ResponseTooLarge {
+        /// The configured limit in bytes.
+        limit: usize,
+    },
+    /// A response was missing its `X-Signature` header or the signature
+    /// didn't match the body.
+    #[error("LycentoError: {SIGNATURE_MISMATCH}")]
+    SignatureMismatch,
+    /// An offline license token is malformed or can't be checked.
+    #[error("LycentoError: invalid offline token - {0}")]
+    InvalidToken(String),
+    /// The license in an offline token has expired.
+    #[error("LycentoError: {LICENSE_EXPIRED} (expired at {expires_at})")]
+    LicenseExpired {
+        /// Expiration date from the token.
+        expires_at: String,
+    },
+    /// No OS keychain could be used to store or load a license key, e.g.
+    /// because the platform has none or it is locked.
+    #[error("LycentoError: no keychain available - {0}")]
+    KeyringUnavailable(String),
+    /// A polling operation didn't complete within its timeout.
+    #[error("LycentoError: {TIMED_OUT} (waited {0:?})")]
+    Timeout(Duration),
+    /// The response body didn't have the expected shape.
+    #[error("LycentoError: {0}")]
+    Deserialize(#[from] DeserializeError),
 }
 
 impl LycentoError {
@@ -21,22 +215,178 @@ impl LycentoError {
         Self::Custom(message.into())
     }
 
+    /// Wrap `source` with a message, keeping it as the error source.
+    pub fn wrap(message: impl Into<String>, source: impl Into<BoxError>) -> Self {
+        Self::Wrapped {
+            message: message.into(),
+            source: source.into(),
+        }
+    }
+
     pub fn message(&self) -> &str {
         match self {
             Self::Custom(msg) => msg,
+            Self::InvalidConfig(msg) => msg,
+            Self::Wrapped { message, .. } => message,
+            Self::Http { message, .. } => message,
+            Self::Unauthorized { message } | Self::Forbidden { message } => message,
+            Self::Network(err) => err.message(),
+            Self::Activation(err) => err.message(),
+            Self::Validation(err) => err.message(),
+            Self::Deactivation(err) => err.message(),
+            Self::TransferFailed { source, .. } => source.message(),
+            Self::RateLimited { .. } => RATE_LIMITED,
+            Self::ResponseTooLarge { .. } => RESPONSE_TOO_LARGE,
+            Self::SignatureMismatch => SIGNATURE_MISMATCH,
+            Self::InvalidToken(msg) => msg,
+            Self::KeyringUnavailable(msg) => msg,
+            Self::LicenseExpired { .. } => LICENSE_EXPIRED,
+            Self::Timeout(_) => TIMED_OUT,
+            Self::Deserialize(_) => UNEXPECTED_RESPONSE,
+        }
+    }
+
+    /// Machine-readable category of this error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::Custom(msg) => ErrorCode::from_message(msg),
+            Self::Http { status, message, reason } => {
+                ErrorCode::from_status(*status, reason.as_deref().unwrap_or(message))
+            }
+            Self::Network(_) | Self::Timeout(_) => ErrorCode::Network,
+            Self::Activation(err) => err.code(),
+            Self::Validation(err) => err.code(),
+            Self::Deactivation(err) => err.code(),
+            Self::Unauthorized { .. } | Self::Forbidden { .. } => ErrorCode::Auth,
+            Self::RateLimited { .. } => ErrorCode::RateLimited,
+            Self::TransferFailed { source, .. } => source.code(),
+            Self::LicenseExpired { .. } => ErrorCode::Expired,
+            Self::InvalidConfig(_)
+            | Self::Wrapped { .. }
+            | Self::ResponseTooLarge { .. }
+            | Self::SignatureMismatch
+            | Self::InvalidToken(_)
+            | Self::KeyringUnavailable(_)
+            | Self::Deserialize(_) => ErrorCode::Unknown,
+        }
+    }
+
+    /// Whether the server could not be reached (connection failure or timeout).
+    pub fn is_network(&self) -> bool {
+        matches!(self, Self::Network(_))
+    }
+
+    /// HTTP status code returned by the server, if this is an HTTP error.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Http { status, .. } => Some(*status),
+            Self::Unauthorized { .. } => Some(401),
+            Self::Forbidden { .. } => Some(403),
+            Self::RateLimited { .. } => Some(429),
+            _ => None,
+        }
+    }
+
+    /// Copy this error for callers that shared one request.
+    ///
+    /// Error sources can't be cloned, so errors that wrap one are copied as
+    /// [`Wrapped`](Self::Wrapped) with the source replaced by its message.
+    pub(crate) fn duplicate(&self) -> Self {
+        match self {
+            Self::Custom(msg) => Self::Custom(msg.clone()),
+            Self::InvalidConfig(msg) => Self::InvalidConfig(msg.clone()),
+            Self::Http { status, message, reason } => Self::Http {
+                status: *status,
+                message: message.clone(),
+                reason: reason.clone(),
+            },
+            Self::Network(err) => Self::Network(err.duplicate()),
+            Self::Validation(err) => Self::Validation(err.duplicate()),
+            Self::Unauthorized { message } => Self::Unauthorized { message: message.clone() },
+            Self::Forbidden { message } => Self::Forbidden { message: message.clone() },
+            Self::RateLimited { retry_after } => Self::RateLimited {
+                retry_after: *retry_after,
+            },
+            Self::ResponseTooLarge { limit } => Self::ResponseTooLarge { limit: *limit },
+            Self::SignatureMismatch => Self::SignatureMismatch,
+            Self::InvalidToken(msg) => Self::InvalidToken(msg.clone()),
+            Self::LicenseExpired { expires_at } => Self::LicenseExpired {
+                expires_at: expires_at.clone(),
+            },
+            Self::KeyringUnavailable(msg) => Self::KeyringUnavailable(msg.clone()),
+            Self::Timeout(waited) => Self::Timeout(*waited),
+            Self::Deserialize(err) => Self::Deserialize(err.duplicate()),
+            err => Self::Wrapped {
+                message: err.message().to_string(),
+                source: Box::new(SourceMessage(err.to_string())),
+            },
         }
     }
 }
 
 impl From<reqwest::Error> for LycentoError {
     fn from(err: reqwest::Error) -> Self {
-        LycentoError::Custom(err.to_string())
+        LycentoError::wrap(err.to_string(), err)
     }
 }
 
 impl From<serde_json::Error> for LycentoError {
     fn from(err: serde_json::Error) -> Self {
-        LycentoError::Custom(err.to_string())
+        LycentoError::wrap(err.to_string(), err)
+    }
+}
+
+impl From<ActivationError> for LycentoError {
+    fn from(err: ActivationError) -> Self {
+        match err {
+            ActivationError::Network(err) => LycentoError::Network(err),
+            ActivationError::Request(err) => *err,
+            err => LycentoError::Activation(err),
+        }
+    }
+}
+
+impl From<ValidationError> for LycentoError {
+    fn from(err: ValidationError) -> Self {
+        match err {
+            ValidationError::Network(err) => LycentoError::Network(err),
+            ValidationError::SignatureMismatch => LycentoError::SignatureMismatch,
+            ValidationError::Request(err) => *err,
+            err => LycentoError::Validation(err),
+        }
+    }
+}
+
+impl From<DeactivationError> for LycentoError {
+    fn from(err: DeactivationError) -> Self {
+        match err {
+            DeactivationError::Network(err) => LycentoError::Network(err),
+            DeactivationError::Request(err) => *err,
+            err => LycentoError::Deactivation(err),
+        }
+    }
+}
+
+/// A response body that didn't have the expected shape.
+///
+/// `body` holds the JSON that failed to parse, which may include the license
+/// key.
+#[derive(Debug, Error)]
+#[error("unexpected response format - {source}")]
+pub struct DeserializeError {
+    /// The underlying parse error.
+    pub source: serde_json::Error,
+    /// The response body that failed to parse.
+    pub body: serde_json::Value,
+}
+
+impl DeserializeError {
+    /// Copy this error, replacing the parse error by one with its message.
+    pub(crate) fn duplicate(&self) -> Self {
+        Self {
+            source: serde::de::Error::custom(&self.source),
+            body: self.body.clone(),
+        }
     }
 }
 
@@ -45,6 +395,19 @@ impl From<serde_json::Error> for LycentoError {
 pub enum ActivationError {
     #[error("ActivationError: {0}")]
     Custom(String),
+    /// The caller cancelled the activation before it completed.
+    #[error("ActivationError: activation cancelled")]
+    Cancelled,
+    /// The server could not be reached.
+    #[error("ActivationError: {0}")]
+    Network(#[source] NetworkError),
+    /// The request failed before the server could accept or reject the
+    /// activation, e.g. because the API key was rejected.
+    #[error("ActivationError: {}", .0.message())]
+    Request(#[source] Box<LycentoError>),
+    /// The response body didn't have the expected shape.
+    #[error("ActivationError: {0}")]
+    Deserialize(#[source] DeserializeError),
 }
 
 impl ActivationError {
@@ -55,13 +418,33 @@ impl ActivationError {
     pub fn message(&self) -> &str {
         match self {
             Self::Custom(msg) => msg,
+            Self::Cancelled => CANCELLED,
+            Self::Network(err) => err.message(),
+            Self::Request(err) => err.message(),
+            Self::Deserialize(_) => UNEXPECTED_RESPONSE,
+        }
+    }
+
+    /// Machine-readable category of this error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::Custom(msg) => ErrorCode::from_message(msg),
+            Self::Cancelled => ErrorCode::Cancelled,
+            Self::Network(_) => ErrorCode::Network,
+            Self::Request(err) => err.code(),
+            Self::Deserialize(_) => ErrorCode::Unknown,
         }
     }
 }
 
 impl From<LycentoError> for ActivationError {
     fn from(err: LycentoError) -> Self {
-        ActivationError::Custom(err.message().to_string())
+        match err {
+            LycentoError::Activation(err) => err,
+            LycentoError::Network(err) => ActivationError::Network(err),
+            LycentoError::Deserialize(err) => ActivationError::Deserialize(err),
+            err => ActivationError::Request(Box::new(err)),
+        }
     }
 }
 
@@ -70,6 +453,27 @@ impl From<LycentoError> for ActivationError {
 pub enum ValidationError {
     #[error("ValidationError: {0}")]
     Custom(String),
+    /// The server could not be reached.
+    #[error("ValidationError: {0}")]
+    Network(#[source] NetworkError),
+    /// The request failed before the server could validate the license,
+    /// e.g. because the API key was rejected.
+    #[error("ValidationError: {}", .0.message())]
+    Request(#[source] Box<LycentoError>),
+    /// The cached offline validation is older than the allowed grace period.
+    #[error("ValidationError: offline grace period of {grace_period:?} exceeded")]
+    OfflineGracePeriodExceeded {
+        /// When the license was last validated online.
+        validated_at: SystemTime,
+        /// The grace period that was exceeded.
+        grace_period: Duration,
+    },
+    /// The validation response failed signature verification.
+    #[error("ValidationError: {SIGNATURE_MISMATCH}")]
+    SignatureMismatch,
+    /// The response body didn't have the expected shape.
+    #[error("ValidationError: {0}")]
+    Deserialize(#[source] DeserializeError),
 }
 
 impl ValidationError {
@@ -80,13 +484,59 @@ impl ValidationError {
     pub fn message(&self) -> &str {
         match self {
             Self::Custom(msg) => msg,
+            Self::Network(err) => err.message(),
+            Self::Request(err) => err.message(),
+            Self::OfflineGracePeriodExceeded { .. } => {
+                "Offline grace period exceeded - please reconnect to validate your license"
+            }
+            Self::SignatureMismatch => SIGNATURE_MISMATCH,
+            Self::Deserialize(_) => UNEXPECTED_RESPONSE,
+        }
+    }
+
+    /// Machine-readable category of this error.
+    ///
+    /// An exceeded offline grace period counts as [`ErrorCode::Network`], as
+    /// reconnecting resolves it.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::Custom(msg) => ErrorCode::from_message(msg),
+            Self::Network(_) | Self::OfflineGracePeriodExceeded { .. } => ErrorCode::Network,
+            Self::Request(err) => err.code(),
+            Self::SignatureMismatch | Self::Deserialize(_) => ErrorCode::Unknown,
+        }
+    }
+
+    /// Copy this error for callers that shared one request.
+    ///
+    /// Error sources can't be cloned, so they are replaced by their message.
+    pub(crate) fn duplicate(&self) -> Self {
+        match self {
+            Self::Custom(msg) => Self::Custom(msg.clone()),
+            Self::Network(err) => Self::Network(err.duplicate()),
+            Self::Request(err) => Self::Request(Box::new(err.duplicate())),
+            Self::OfflineGracePeriodExceeded {
+                validated_at,
+                grace_period,
+            } => Self::OfflineGracePeriodExceeded {
+                validated_at: *validated_at,
+                grace_period: *grace_period,
+            },
+            Self::SignatureMismatch => Self::SignatureMismatch,
+            Self::Deserialize(err) => Self::Deserialize(err.duplicate()),
         }
     }
 }
 
 impl From<LycentoError> for ValidationError {
     fn from(err: LycentoError) -> Self {
-        ValidationError::Custom(err.message().to_string())
+        match err {
+            LycentoError::Network(err) => ValidationError::Network(err),
+            LycentoError::Validation(err) => err,
+            LycentoError::SignatureMismatch => ValidationError::SignatureMismatch,
+            LycentoError::Deserialize(err) => ValidationError::Deserialize(err),
+            err => ValidationError::Request(Box::new(err)),
+        }
     }
 }
 
@@ -95,6 +545,16 @@ impl From<LycentoError> for ValidationError {
 pub enum DeactivationError {
     #[error("DeactivationError: {0}")]
     Custom(String),
+    /// The server could not be reached.
+    #[error("DeactivationError: {0}")]
+    Network(#[source] NetworkError),
+    /// The request failed before the server could accept or reject the
+    /// deactivation, e.g. because the API key was rejected.
+    #[error("DeactivationError: {}", .0.message())]
+    Request(#[source] Box<LycentoError>),
+    /// The response body didn't have the expected shape.
+    #[error("DeactivationError: {0}")]
+    Deserialize(#[source] DeserializeError),
 }
 
 impl DeactivationError {
@@ -105,21 +565,71 @@ impl DeactivationError {
     pub fn message(&self) -> &str {
         match self {
             Self::Custom(msg) => msg,
+            Self::Network(err) => err.message(),
+            Self::Request(err) => err.message(),
+            Self::Deserialize(_) => UNEXPECTED_RESPONSE,
+        }
+    }
+
+    /// Machine-readable category of this error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::Custom(msg) => ErrorCode::from_message(msg),
+            Self::Network(_) => ErrorCode::Network,
+            Self::Request(err) => err.code(),
+            Self::Deserialize(_) => ErrorCode::Unknown,
         }
     }
 }
 
 impl From<LycentoError> for DeactivationError {
     fn from(err: LycentoError) -> Self {
-        DeactivationError::Custom(err.message().to_string())
+        match err {
+            LycentoError::Deactivation(err) => err,
+            LycentoError::Network(err) => DeactivationError::Network(err),
+            LycentoError::Deserialize(err) => DeactivationError::Deserialize(err),
+            err => DeactivationError::Request(Box::new(err)),
+        }
     }
 }
 
 /// Error type for network-related failures.
+///
+/// Transport failures are classified so callers can tell a slow server
+/// (`Timeout`) from a missing connection (`Connect`). The underlying
+/// `reqwest::Error` is available through `source()`.
 #[derive(Debug, Error)]
 pub enum NetworkError {
     #[error("NetworkError: {0}")]
     Custom(String),
+    /// The request timed out.
+    #[error("NetworkError: {message}")]
+    Timeout {
+        message: String,
+        #[source]
+        source: BoxError,
+    },
+    /// The connection to the server could not be established.
+    #[error("NetworkError: {message}")]
+    Connect {
+        message: String,
+        #[source]
+        source: BoxError,
+    },
+    /// The request could not be sent.
+    #[error("NetworkError: {message}")]
+    Request {
+        message: String,
+        #[source]
+        source: BoxError,
+    },
+    /// Any other transport failure.
+    #[error("NetworkError: {message}")]
+    Other {
+        message: String,
+        #[source]
+        source: BoxError,
+    },
 }
 
 impl NetworkError {
@@ -127,16 +637,97 @@ impl NetworkError {
         Self::Custom(message.into())
     }
 
+    /// Wrap `source` with a message as an `Other` error, keeping it as the
+    /// error source.
+    pub fn wrap(message: impl Into<String>, source: impl Into<BoxError>) -> Self {
+        Self::Other {
+            message: message.into(),
+            source: source.into(),
+        }
+    }
+
     pub fn message(&self) -> &str {
         match self {
             Self::Custom(msg) => msg,
+            Self::Timeout { message, .. }
+            | Self::Connect { message, .. }
+            | Self::Request { message, .. }
+            | Self::Other { message, .. } => message,
+        }
+    }
+
+    /// Machine-readable category of this error, always [`ErrorCode::Network`].
+    pub fn code(&self) -> ErrorCode {
+        ErrorCode::Network
+    }
+
+    /// Whether the request timed out.
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Self::Timeout { .. })
+    }
+
+    /// Whether the server could not be connected to.
+    pub fn is_connect(&self) -> bool {
+        matches!(self, Self::Connect { .. })
+    }
+
+    /// Copy this error, replacing its source by the source's message.
+    pub(crate) fn duplicate(&self) -> Self {
+        let source = |source: &BoxError| -> BoxError { Box::new(SourceMessage(source.to_string())) };
+        match self {
+            Self::Custom(msg) => Self::Custom(msg.clone()),
+            Self::Timeout { message, source: s } => Self::Timeout {
+                message: message.clone(),
+                source: source(s),
+            },
+            Self::Connect { message, source: s } => Self::Connect {
+                message: message.clone(),
+                source: source(s),
+            },
+            Self::Request { message, source: s } => Self::Request {
+                message: message.clone(),
+                source: source(s),
+            },
+            Self::Other { message, source: s } => Self::Other {
+                message: message.clone(),
+                source: source(s),
+            },
+        }
+    }
+
+    /// Replace the message, keeping the kind and source.
+    pub(crate) fn with_message(self, message: String) -> Self {
+        match self {
+            Self::Custom(_) => Self::Custom(message),
+            Self::Timeout { source, .. } => Self::Timeout { message, source },
+            Self::Connect { source, .. } => Self::Connect { message, source },
+            Self::Request { source, .. } => Self::Request { message, source },
+            Self::Other { source, .. } => Self::Other { message, source },
         }
     }
 }
 
 impl From<reqwest::Error> for NetworkError {
     fn from(err: reqwest::Error) -> Self {
-        NetworkError::Custom(err.to_string())
+        // Checked first, since a connect timeout is also a connect error.
+        if err.is_timeout() {
+            NetworkError::Timeout {
+                message: "Request timed out - the server is slow to respond, please try again".to_string(),
+                source: err.into(),
+            }
+        } else if is_connect(&err) {
+            NetworkError::Connect {
+                message: "Could not connect to the server - please check your connection".to_string(),
+                source: err.into(),
+            }
+        } else if err.is_request() {
+            NetworkError::Request {
+                message: "Network error - please check your connection".to_string(),
+                source: err.into(),
+            }
+        } else {
+            NetworkError::wrap(err.to_string(), err)
+        }
     }
 }
 
@@ -159,3 +750,80 @@ pub type DeactivationResult<T> = std::result::Result<T, DeactivationError>;
 /// Specialized result type for network operations.
 #[allow(dead_code)]
 pub type NetworkResult<T> = std::result::Result<T, NetworkError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activate() -> Result<(), ActivationError> {
+        Err(ActivationError::new("Device limit reached"))
+    }
+
+    fn unified() -> Result<(), LycentoError> {
+        activate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_specialized_errors_convert_to_base() {
+        let err = unified().unwrap_err();
+        assert_eq!(err.message(), "Device limit reached");
+        assert_eq!(err.to_string(), "ActivationError: Device limit reached");
+        assert!(matches!(ActivationError::from(err), ActivationError::Custom(_)));
+
+        let err = LycentoError::from(ValidationError::Network(NetworkError::new("offline")));
+        assert!(err.is_network());
+
+        let err = ValidationError::from(LycentoError::SignatureMismatch);
+        assert!(matches!(LycentoError::from(err), LycentoError::SignatureMismatch));
+
+        let err = LycentoError::from(DeactivationError::new("Not activated"));
+        assert_eq!(err.message(), "Not activated");
+    }
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(unified().unwrap_err().code(), ErrorCode::DeviceLimit);
+        assert_eq!(ActivationError::new("device_limit_exceeded").code(), ErrorCode::DeviceLimit);
+        assert_eq!(ActivationError::Cancelled.code(), ErrorCode::Cancelled);
+
+        let not_found = LycentoError::Http {
+            status: 404,
+            message: "License not found".to_string(),
+            reason: None,
+        };
+        assert_eq!(not_found.code(), ErrorCode::NotFound);
+        // Survives conversion to a specialized error, which keeps the status
+        let not_found = DeactivationError::from(not_found);
+        assert_eq!(not_found.code(), ErrorCode::NotFound);
+        assert_eq!(LycentoError::from(not_found).status(), Some(404));
+
+        let offline = ActivationError::from(LycentoError::Network(NetworkError::new("offline")));
+        assert_eq!(offline.code(), ErrorCode::Network);
+        assert!(LycentoError::from(offline).is_network());
+        let rejected = ActivationError::from(LycentoError::Unauthorized { message: String::new() });
+        assert!(matches!(rejected, ActivationError::Request(ref err) if matches!(**err, LycentoError::Unauthorized { .. })));
+        assert_eq!(rejected.code(), ErrorCode::Auth);
+
+        let expired = LycentoError::Http {
+            status: 422,
+            message: "License has expired".to_string(),
+            reason: None,
+        };
+        assert_eq!(expired.code(), ErrorCode::Expired);
+        let full = LycentoError::Http {
+            status: 422,
+            message: "Limite de dispositivos atingido".to_string(),
+            reason: Some("device_limit_exceeded".to_string()),
+        };
+        assert_eq!(full.code(), ErrorCode::DeviceLimit);
+        assert_eq!(LycentoError::RateLimited { retry_after: None }.code(), ErrorCode::RateLimited);
+        assert_eq!(LycentoError::Forbidden { message: String::new() }.code(), ErrorCode::Auth);
+        assert_eq!(ValidationError::Network(NetworkError::new("offline")).code(), ErrorCode::Network);
+        let limited = ValidationError::from(LycentoError::RateLimited { retry_after: None });
+        assert_eq!(limited.code(), ErrorCode::RateLimited);
+        assert_eq!(limited.duplicate().code(), ErrorCode::RateLimited);
+        assert_eq!(LycentoError::SignatureMismatch.code(), ErrorCode::Unknown);
+        assert_eq!(ErrorCode::DeviceLimit.to_string(), "device_limit");
+    }
+}
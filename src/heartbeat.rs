@@ -0,0 +1,318 @@
+//! Background license heartbeat for the Lycento SDK.
+//!
+//! This module provides `HeartbeatHandle` and `HeartbeatEvent`, used by
+//! `LycentoClient::start_heartbeat` to periodically re-validate a license so
+//! that revocations take effect without restarting long-running apps, and
+//! `LicenseState`, yielded by the `LycentoClient::watch` stream. The
+//! background task needs a tokio runtime and is not available on WASM.
+//!
+//! Background tasks are tracked per client (shared by its clones) so that
+//! `LycentoClient::shutdown` can cancel them and wait for them to exit.
+
+use crate::client::{LicenseStatusKind, ValidateResponse, ValidationFailureReason};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::errors::LycentoError;
+use crate::errors::ValidationError;
+#[cfg(not(target_arch = "wasm32"))]
+use std::future::Future;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::sync::{oneshot, watch};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::task::JoinHandle;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::MissedTickBehavior;
+
+/// Outcome of a single heartbeat validation.
+#[derive(Debug)]
+pub enum HeartbeatEvent {
+    /// The license is still valid.
+    Valid(ValidateResponse),
+    /// The license is no longer valid (`valid: false` or revoked).
+    Invalid(ValidateResponse),
+    /// The validation request failed.
+    Error(ValidationError),
+}
+
+impl HeartbeatEvent {
+    /// Whether the app should lock the UI in response to this event.
+    pub fn is_invalid(&self) -> bool {
+        matches!(self, Self::Invalid(_))
+    }
+}
+
+impl From<Result<ValidateResponse, ValidationError>> for HeartbeatEvent {
+    fn from(result: Result<ValidateResponse, ValidationError>) -> Self {
+        match result {
+            Ok(response) if !response.valid || response.license.status_enum() == LicenseStatusKind::Revoked => {
+                Self::Invalid(response)
+            }
+            Ok(response) => Self::Valid(response),
+            Err(error) => Self::Error(error),
+        }
+    }
+}
+
+/// Coarse state of a license, as reported by `LycentoClient::watch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LicenseState {
+    /// The license is valid.
+    Valid,
+    /// The license has expired.
+    Expired,
+    /// The license has been revoked.
+    Revoked,
+    /// The license is invalid for another reason, or it couldn't be validated.
+    Unknown,
+}
+
+impl From<&Result<ValidateResponse, ValidationError>> for LicenseState {
+    fn from(result: &Result<ValidateResponse, ValidationError>) -> Self {
+        let response = match result {
+            Ok(response) => response,
+            Err(_) => return Self::Unknown,
+        };
+
+        let status = response.license.status_enum();
+        if status == LicenseStatusKind::Revoked || response.reason == Some(ValidationFailureReason::Revoked) {
+            Self::Revoked
+        } else if status == LicenseStatusKind::Expired || response.reason == Some(ValidationFailureReason::Expired) {
+            Self::Expired
+        } else if response.valid {
+            Self::Valid
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+/// Background tasks spawned by a client and its clones.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub(crate) struct BackgroundTasks {
+    /// Set to `true` once the client shuts down.
+    shutdown: watch::Sender<bool>,
+    /// Number of tasks that haven't exited yet.
+    running: watch::Sender<usize>,
+}
+
+/// Marks a background task as running until dropped.
+#[cfg(not(target_arch = "wasm32"))]
+struct Running(Arc<BackgroundTasks>);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl BackgroundTasks {
+    pub(crate) fn new() -> Self {
+        Self {
+            shutdown: watch::channel(false).0,
+            running: watch::channel(0).0,
+        }
+    }
+
+    /// Cancel every task and wait until all of them have exited.
+    ///
+    /// Tasks started afterwards exit immediately.
+    pub(crate) async fn shutdown(&self) {
+        self.shutdown.send_replace(true);
+        let _ = self.running.subscribe().wait_for(|running| *running == 0).await;
+    }
+
+    fn start(self: &Arc<Self>) -> Running {
+        self.running.send_modify(|running| *running += 1);
+        Running(self.clone())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for Running {
+    fn drop(&mut self) {
+        self.0.running.send_modify(|running| *running -= 1);
+    }
+}
+
+/// Handle to a running heartbeat task.
+///
+/// Dropping the handle stops the heartbeat as well, without waiting for it.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct HeartbeatHandle {
+    stop: Option<oneshot::Sender<()>>,
+    task: JoinHandle<()>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl HeartbeatHandle {
+    /// Stop the heartbeat and wait for its task to exit.
+    ///
+    /// A validation that is already in flight is allowed to finish first.
+    pub async fn stop(mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        let _ = (&mut self.task).await;
+    }
+
+    /// Whether the heartbeat task has exited.
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+/// Spawn a task that runs `check` every `interval` and reports each result.
+///
+/// The first check happens one `interval` after spawning. Shutting down
+/// `tasks` cancels the task, including a check in flight. A zero `interval`
+/// is rejected, as the task would check continuously.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn spawn_heartbeat<C, Fut, F>(
+    tasks: &Arc<BackgroundTasks>,
+    interval: Duration,
+    check: C,
+    on_event: F,
+) -> Result<HeartbeatHandle, LycentoError>
+where
+    C: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<ValidateResponse, ValidationError>> + Send,
+    F: Fn(HeartbeatEvent) + Send + Sync + 'static,
+{
+    if interval.is_zero() {
+        return Err(LycentoError::InvalidConfig(
+            "heartbeat interval must be greater than zero".to_string(),
+        ));
+    }
+
+    let (stop_tx, mut stop_rx) = oneshot::channel();
+    let mut shutdown = tasks.shutdown.subscribe();
+    let running = tasks.start();
+
+    let task = tokio::spawn(async move {
+        let _running = running;
+        let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = &mut stop_rx => break,
+                _ = shutdown.wait_for(|shutdown| *shutdown) => break,
+                _ = ticker.tick() => {}
+            }
+
+            let result = tokio::select! {
+                _ = shutdown.wait_for(|shutdown| *shutdown) => break,
+                result = check() => result,
+            };
+            on_event(HeartbeatEvent::from(result));
+        }
+    });
+
+    Ok(HeartbeatHandle {
+        stop: Some(stop_tx),
+        task,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    fn response(valid: bool) -> ValidateResponse {
+        serde_json::from_value(serde_json::json!({
+            "valid": valid,
+            "license": {
+                "key": "TEST-KEY",
+                "status": if valid { "active" } else { "revoked" },
+                "type": "perpetual",
+                "expiresAt": null,
+                "maxDevices": 3
+            }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_heartbeat_runs_on_interval() {
+        let interval = Duration::from_secs(3600);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let tasks = Arc::new(BackgroundTasks::new());
+        let handle = spawn_heartbeat(
+            &tasks,
+            interval,
+            {
+                let calls = calls.clone();
+                move || {
+                    let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                    async move { Ok(response(call < 3)) }
+                }
+            },
+            {
+                let events = events.clone();
+                move |event: HeartbeatEvent| events.lock().unwrap().push(event.is_invalid())
+            },
+        )
+        .unwrap();
+
+        tokio::time::sleep(interval / 2).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        tokio::time::sleep(interval * 3).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(*events.lock().unwrap(), vec![false, false, true]);
+
+        handle.stop().await;
+        tokio::time::sleep(interval * 2).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_shutdown_cancels_heartbeats() {
+        let interval = Duration::from_secs(3600);
+        let tasks = Arc::new(BackgroundTasks::new());
+        let spawn = || {
+            spawn_heartbeat(
+                &tasks,
+                interval,
+                || async { std::future::pending().await },
+                |_: HeartbeatEvent| {},
+            )
+            .unwrap()
+        };
+        let handles = [spawn(), spawn()];
+
+        // Both checks are in flight and never finish on their own
+        tokio::time::sleep(interval * 2).await;
+        tasks.shutdown().await;
+        assert!(handles.iter().all(HeartbeatHandle::is_finished));
+
+        // Heartbeats started after shutdown exit right away
+        assert!(tokio::time::timeout(interval, spawn().stop()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_rejects_zero_interval() {
+        let tasks = Arc::new(BackgroundTasks::new());
+        let result = spawn_heartbeat(
+            &tasks,
+            Duration::ZERO,
+            || async { Ok(response(true)) },
+            |_: HeartbeatEvent| {},
+        );
+        assert!(matches!(result, Err(LycentoError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_license_state() {
+        assert_eq!(LicenseState::from(&Ok(response(true))), LicenseState::Valid);
+        assert_eq!(LicenseState::from(&Ok(response(false))), LicenseState::Revoked);
+        assert_eq!(
+            LicenseState::from(&Err(ValidationError::new("boom"))),
+            LicenseState::Unknown
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! License key storage in the OS keychain, behind the `keyring` feature.
+//!
+//! Keys are stored with the `keyring` crate: in the Keychain on macOS and iOS,
+//! the Credential Manager on Windows and the Secret Service on Linux, cached in
+//! the kernel keyring. Linux builds need libdbus, unless the `keyring-vendored`
+//! feature builds it from source. Other platforms have no keychain, and every
+//! call fails with `LycentoError::KeyringUnavailable`.
+
+use crate::errors::LycentoError;
+
+/// Account the license key is stored under within a service.
+const LICENSE_KEY_ACCOUNT: &str = "license-key";
+
+/// Store `license_key` under `service`, replacing any key stored before.
+pub(crate) fn store_license_key(service: &str, license_key: &str) -> Result<(), LycentoError> {
+    entry(service)?.set_password(license_key).map_err(keyring_error)
+}
+
+/// Load the license key stored under `service`, or `None` if there is none.
+pub(crate) fn load_license_key(service: &str) -> Result<Option<String>, LycentoError> {
+    match entry(service)?.get_password() {
+        Ok(license_key) => Ok(Some(license_key)),
+        Err(::keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(keyring_error(e)),
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "ios", target_os = "windows"))]
+fn entry(service: &str) -> Result<::keyring::Entry, LycentoError> {
+    ::keyring::Entry::new(service, LICENSE_KEY_ACCOUNT).map_err(keyring_error)
+}
+
+/// The `keyring` crate falls back to an in-memory store here, which would
+/// silently lose the key.
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "ios", target_os = "windows")))]
+fn entry(_service: &str) -> Result<::keyring::Entry, LycentoError> {
+    Err(LycentoError::KeyringUnavailable(
+        "no OS keychain is supported on this platform".to_string(),
+    ))
+}
+
+fn keyring_error(error: ::keyring::Error) -> LycentoError {
+    match error {
+        ::keyring::Error::NoStorageAccess(e) | ::keyring::Error::PlatformFailure(e) => {
+            LycentoError::KeyringUnavailable(e.to_string())
+        }
+        e => LycentoError::wrap("Failed to access the license key in the OS keychain", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyring_errors() {
+        let locked = ::keyring::Error::NoStorageAccess("keychain is locked".into());
+        assert!(matches!(
+            keyring_error(locked),
+            LycentoError::KeyringUnavailable(message) if message == "keychain is locked"
+        ));
+
+        let invalid = ::keyring::Error::Invalid("service".to_string(), "empty".to_string());
+        assert!(matches!(keyring_error(invalid), LycentoError::Wrapped { .. }));
+    }
+}
@@ -13,7 +13,7 @@
 //!
 //! ## Quick Start
 //!
-//! ```rust
+//! ```rust,no_run
 //! use lycento_sdk::{LycentoClient, LycentoConfig};
 //!
 //! #[tokio::main]
@@ -38,36 +38,89 @@
 //!
 //! ## Tauri Integration
 //!
-//! This SDK is designed to work seamlessly with Tauri applications. You can
-//! expose the client functionality to your frontend using Tauri commands.
+//! This SDK is designed to work seamlessly with Tauri applications. With the
+//! `tauri` feature, `LycentoPlugin` registers a client and ready-made
+//! commands for the frontend.
 //!
 //! ## Modules
 //!
 //! - [`client`] - Main client for license operations
+//! - `blocking` - Synchronous client (requires the `blocking` feature)
+//! - `mock` - In-memory `LicenseClient` for tests (requires the `mock` feature)
+//! - `tauri` - Tauri plugin and commands (requires the `tauri` feature)
 //! - [`device`] - Device information and identification
 //! - [`errors`] - Error types
+//! - `offline` - Offline validation cache ([`OfflineCacheConfig`], [`LycentoClient::validate_offline`])
 
 // Re-export public API
 pub use crate::client::{
-    create_client, validate_license, ActivateOptions, ActivateResponse, DeactivateOptions,
-    DeactivateResponse, LicenseInfo, LicenseInfoResponse, LycentoClient, LycentoConfig,
-    ValidateOptions, ValidateResponse,
+    create_client, validate_license, validate_license_with_config, ActivateOptions, ActivateResponse, ActivationPage,
+    ActivationPreflight, AuthScheme, DeactivateOptions, DeactivateOthersSummary, DeactivateResponse, LicenseClient,
+    LicenseInfo, LicenseInfoResponse, LicenseStatus, LicenseStatusKind, LicenseType, LycentoClient,
+    LycentoClientBuilder, LycentoConfig, PreflightSource, RenewOptions, RenewResponse, RequestEvent, RequestObserver,
+    ResponseMeta, ValidateOptions, ValidateResponse, ValidationFailureReason,
 };
 
 pub use crate::device::{
-    generate_device_id, get_device_id, get_device_info, get_device_name, get_platform, get_platform_version,
-    hash_string, simple_hash, DeviceInfo, Platform,
+    device_id_version, device_ids_match, get_architecture, get_device_info_for, get_device_name, get_local_ip,
+    get_persistent_device_id, get_platform, get_platform_version, get_raw_device_name, hash_string,
+    legacy_device_id, sanitize_device_name, simple_hash, Architecture, DEVICE_ID_VERSION,
+    DeviceInfo, PersistentDeviceId, Platform,
+};
+
+#[cfg(feature = "fingerprint")]
+pub use crate::device::{
+    generate_device_id, get_device_id, get_device_info, get_machine_id, is_virtual_machine, refresh_device_id,
+    DeviceIdBuilder,
 };
 
 pub use crate::client::{ActivationDetails, ActivationRecord};
 
 pub use crate::errors::{
-    ActivationError, DeactivationError, LycentoError, NetworkError, ValidationError,
+    ActivationError, BoxError, DeactivationError, DeserializeError, ErrorCode, LycentoError, NetworkError,
+    ValidationError,
+};
+
+pub use crate::heartbeat::{HeartbeatEvent, LicenseState};
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::heartbeat::HeartbeatHandle;
+
+pub use crate::offline::{OfflineCacheConfig, OfflineLicense};
+#[cfg(feature = "chrono")]
+pub use crate::offline::verify_offline_token;
+
+pub use crate::token::{AccessToken, TokenProvider, TokenSource};
+
+#[cfg(feature = "blocking")]
+pub use crate::blocking::LycentoClientBlocking;
+
+#[cfg(feature = "mock")]
+pub use crate::mock::MockLicenseClient;
+
+#[cfg(feature = "tauri")]
+pub use crate::tauri::{
+    lycento_activate, lycento_deactivate, lycento_get_info, lycento_validate, CommandError, LycentoPlugin,
 };
 
+#[cfg(feature = "blocking")]
+mod blocking;
+mod cache;
 mod client;
 mod device;
+mod dry_run;
 mod errors;
+mod heartbeat;
+#[cfg(feature = "keyring")]
+mod keychain;
+mod logging;
+#[cfg(feature = "mock")]
+mod mock;
+mod offline;
+mod retry;
+mod single_flight;
+#[cfg(feature = "tauri")]
+mod tauri;
+mod token;
 
 // Re-export version info
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -0,0 +1,226 @@
+//! Logging helpers for the Lycento SDK.
+//!
+//! Requests and responses are logged through the `log` facade: method, URL
+//! and status at debug level, bodies at trace level. License keys are
+//! redacted unless disabled via `LycentoConfig::with_redact_license_keys`.
+//!
+//! With the `tracing` feature, every API request of both clients runs in a
+//! `lycento.request` span; the helpers here open it and record its fields
+//! and retry events.
+//!
+//! With the `metrics` feature, every API request increments
+//! `lycento.<endpoint>.success` or `lycento.<endpoint>.failure` and records
+//! its latency, retries included, in the `lycento.<endpoint>.duration_seconds`
+//! histogram, where `<endpoint>` is e.g. `activate`, `validate` or `info`.
+//! Latency isn't recorded on WASM.
+
+use serde_json::Value;
+use std::time::Duration;
+
+/// JSON fields that carry a license key.
+const LICENSE_KEY_FIELDS: &[&str] = &["license_key", "licenseKey", "key"];
+
+/// Mask a license key, keeping only its last four characters.
+pub(crate) fn redact_license_key(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    if chars.len() <= 8 {
+        return "****".to_string();
+    }
+
+    let suffix: String = chars[chars.len() - 4..].iter().collect();
+    format!("****{}", suffix)
+}
+
+/// Return a copy of `value` with every license key field masked.
+pub(crate) fn redact_json(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        Value::String(key) if LICENSE_KEY_FIELDS.contains(&name.as_str()) => {
+                            Value::String(redact_license_key(key))
+                        }
+                        other => redact_json(other),
+                    };
+                    (name.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact_json).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Log a request or response body at trace level.
+pub(crate) fn trace_body(label: &str, body: &Value, redact: bool) {
+    if !log::log_enabled!(log::Level::Trace) {
+        return;
+    }
+
+    if redact {
+        log::trace!("{}: {}", label, redact_json(body));
+    } else {
+        log::trace!("{}: {}", label, body);
+    }
+}
+
+/// The `tracing` span wrapping one API request, with `endpoint`,
+/// `license_key`, `device_id` and `status` fields.
+///
+/// Does nothing without the `tracing` feature.
+pub(crate) struct RequestSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+/// Keeps a [`RequestSpan`] entered until dropped.
+#[cfg(feature = "blocking")]
+pub(crate) struct EnteredRequestSpan {
+    #[cfg(feature = "tracing")]
+    _entered: tracing::span::EnteredSpan,
+}
+
+impl RequestSpan {
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn new(endpoint: &str) -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "lycento.request",
+                endpoint,
+                license_key = tracing::field::Empty,
+                device_id = tracing::field::Empty,
+                status = tracing::field::Empty,
+            ),
+        }
+    }
+
+    /// Run `future` in the span.
+    pub(crate) async fn run<F: std::future::Future>(self, future: F) -> F::Output {
+        #[cfg(feature = "tracing")]
+        return tracing::Instrument::instrument(future, self.span).await;
+        #[cfg(not(feature = "tracing"))]
+        return future.await;
+    }
+
+    /// Enter the span for synchronous code. Use [`run`](Self::run) across
+    /// `.await` points instead.
+    #[cfg(feature = "blocking")]
+    pub(crate) fn enter(self) -> EnteredRequestSpan {
+        EnteredRequestSpan {
+            #[cfg(feature = "tracing")]
+            _entered: self.span.entered(),
+        }
+    }
+}
+
+/// Record `value` as `field` on the current `tracing` span.
+///
+/// Does nothing without the `tracing` feature.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn record_span_field(field: &'static str, value: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record(field, value);
+}
+
+/// Report that a request is retried after `delay`.
+pub(crate) fn log_retry(url: &str, attempt: u32, delay: Duration, reason: &str) {
+    log::debug!("Retrying {} in {:?} after attempt {} ({})", url, delay, attempt, reason);
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        url,
+        attempt,
+        delay_ms = delay.as_millis() as u64,
+        reason,
+        "retrying request"
+    );
+}
+
+/// Measures elapsed time. Always reports zero on WASM, where
+/// `std::time::Instant` isn't available.
+#[derive(Debug)]
+pub(crate) struct Stopwatch {
+    #[cfg(not(target_arch = "wasm32"))]
+    started: std::time::Instant,
+}
+
+impl Stopwatch {
+    pub(crate) fn start() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            started: std::time::Instant::now(),
+        }
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.started.elapsed();
+        #[cfg(target_arch = "wasm32")]
+        return Duration::ZERO;
+    }
+}
+
+/// Measures one API request for the `metrics` feature.
+///
+/// Does nothing without the feature.
+pub(crate) struct RequestMetrics {
+    #[cfg(feature = "metrics")]
+    endpoint: String,
+    #[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+    started: std::time::Instant,
+}
+
+impl RequestMetrics {
+    /// Start measuring a request to `path`.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn start(path: &str) -> Self {
+        Self {
+            #[cfg(feature = "metrics")]
+            endpoint: path.rsplit('/').next().unwrap_or(path).to_string(),
+            #[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+            started: std::time::Instant::now(),
+        }
+    }
+
+    /// Record the request's outcome and latency.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn finish(self, success: bool) {
+        #[cfg(feature = "metrics")]
+        {
+            let outcome = if success { "success" } else { "failure" };
+            metrics::counter!(format!("lycento.{}.{}", self.endpoint, outcome)).increment(1);
+
+            #[cfg(not(target_arch = "wasm32"))]
+            metrics::histogram!(format!("lycento.{}.duration_seconds", self.endpoint))
+                .record(self.started.elapsed().as_secs_f64());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_license_key() {
+        assert_eq!(redact_license_key("TEST-ABC123-DEF456"), "****F456");
+        assert_eq!(redact_license_key("SHORT"), "****");
+    }
+
+    #[test]
+    fn test_redact_json() {
+        let body = serde_json::json!({
+            "license_key": "TEST-ABC123-DEF456",
+            "device_id": "abc",
+            "license": { "key": "TEST-ABC123-DEF456", "maxDevices": 3 }
+        });
+
+        let redacted = redact_json(&body);
+        assert_eq!(redacted["license_key"], "****F456");
+        assert_eq!(redacted["device_id"], "abc");
+        assert_eq!(redacted["license"]["key"], "****F456");
+        assert_eq!(redacted["license"]["maxDevices"], 3);
+    }
+}
@@ -0,0 +1,161 @@
+//! Mock client for testing code that uses the Lycento SDK.
+//!
+//! This module provides `MockLicenseClient`, an in-memory implementation of
+//! `LicenseClient` that returns queued responses without touching the
+//! network. It is only available with the `mock` feature.
+
+use crate::client::{
+    ActivateOptions, ActivateResponse, DeactivateOptions, DeactivateResponse, LicenseClient, LicenseInfoResponse,
+    ValidateOptions, ValidateResponse,
+};
+use crate::errors::{ActivationError, DeactivationError, LycentoError, ValidationError};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// A `LicenseClient` that replays canned responses.
+///
+/// Each operation has its own queue; responses and errors are returned in
+/// the order they were queued. Calling an operation with an empty queue
+/// returns an error.
+///
+/// ```rust
+/// use lycento_sdk::{LicenseClient, MockLicenseClient, ValidateOptions, ValidationError};
+/// use std::sync::Arc;
+///
+/// # async fn run() {
+/// let client: Arc<dyn LicenseClient> =
+///     Arc::new(MockLicenseClient::new().with_validate_error(ValidationError::new("revoked")));
+///
+/// assert!(client.validate(ValidateOptions::new("KEY")).await.is_err());
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct MockLicenseClient {
+    activate: Mutex<VecDeque<Result<ActivateResponse, ActivationError>>>,
+    validate: Mutex<VecDeque<Result<ValidateResponse, ValidationError>>>,
+    deactivate: Mutex<VecDeque<Result<DeactivateResponse, DeactivationError>>>,
+    get_info: Mutex<VecDeque<Result<LicenseInfoResponse, LycentoError>>>,
+}
+
+impl MockLicenseClient {
+    /// Create a mock with empty queues.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a successful activation.
+    pub fn with_activate_response(self, response: ActivateResponse) -> Self {
+        push(&self.activate, Ok(response));
+        self
+    }
+
+    /// Queue a failed activation.
+    pub fn with_activate_error(self, error: ActivationError) -> Self {
+        push(&self.activate, Err(error));
+        self
+    }
+
+    /// Queue a successful validation.
+    pub fn with_validate_response(self, response: ValidateResponse) -> Self {
+        push(&self.validate, Ok(response));
+        self
+    }
+
+    /// Queue a failed validation.
+    pub fn with_validate_error(self, error: ValidationError) -> Self {
+        push(&self.validate, Err(error));
+        self
+    }
+
+    /// Queue a successful deactivation.
+    pub fn with_deactivate_response(self, response: DeactivateResponse) -> Self {
+        push(&self.deactivate, Ok(response));
+        self
+    }
+
+    /// Queue a failed deactivation.
+    pub fn with_deactivate_error(self, error: DeactivationError) -> Self {
+        push(&self.deactivate, Err(error));
+        self
+    }
+
+    /// Queue a successful license info lookup.
+    pub fn with_info_response(self, response: LicenseInfoResponse) -> Self {
+        push(&self.get_info, Ok(response));
+        self
+    }
+
+    /// Queue a failed license info lookup.
+    pub fn with_info_error(self, error: LycentoError) -> Self {
+        push(&self.get_info, Err(error));
+        self
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl LicenseClient for MockLicenseClient {
+    async fn activate(&self, _options: ActivateOptions) -> Result<ActivateResponse, ActivationError> {
+        pop(&self.activate).unwrap_or_else(|| Err(ActivationError::new("MockLicenseClient: no activate response queued")))
+    }
+
+    async fn validate(&self, _options: ValidateOptions) -> Result<ValidateResponse, ValidationError> {
+        pop(&self.validate).unwrap_or_else(|| Err(ValidationError::new("MockLicenseClient: no validate response queued")))
+    }
+
+    async fn deactivate(&self, _options: DeactivateOptions) -> Result<DeactivateResponse, DeactivationError> {
+        pop(&self.deactivate)
+            .unwrap_or_else(|| Err(DeactivationError::new("MockLicenseClient: no deactivate response queued")))
+    }
+
+    async fn get_info(&self, _license_key: &str) -> Result<LicenseInfoResponse, LycentoError> {
+        pop(&self.get_info).unwrap_or_else(|| Err(LycentoError::new("MockLicenseClient: no info response queued")))
+    }
+}
+
+fn push<T>(queue: &Mutex<VecDeque<T>>, item: T) {
+    queue.lock().unwrap_or_else(|e| e.into_inner()).push_back(item);
+}
+
+fn pop<T>(queue: &Mutex<VecDeque<T>>) -> Option<T> {
+    queue.lock().unwrap_or_else(|e| e.into_inner()).pop_front()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn valid_response() -> ValidateResponse {
+        serde_json::from_value(serde_json::json!({
+            "valid": true,
+            "license": {
+                "key": "TEST-KEY",
+                "status": "active",
+                "type": "perpetual",
+                "expiresAt": null,
+                "maxDevices": 3
+            }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_mock_replays_queued_results() {
+        let client: Arc<dyn LicenseClient> = Arc::new(
+            MockLicenseClient::new()
+                .with_validate_response(valid_response())
+                .with_validate_error(ValidationError::new("revoked")),
+        );
+
+        let first = client.validate(ValidateOptions::new("TEST-KEY")).await.unwrap();
+        assert!(first.valid);
+
+        let second = client.validate(ValidateOptions::new("TEST-KEY")).await.unwrap_err();
+        assert_eq!(second.message(), "revoked");
+
+        assert!(client.validate(ValidateOptions::new("TEST-KEY")).await.is_err());
+        assert!(client.get_info("TEST-KEY").await.is_err());
+    }
+}
@@ -0,0 +1,340 @@
+//! Offline validation support for the Lycento SDK.
+//!
+//! Successful validations can be cached on disk so that
+//! `LycentoClient::validate_offline` keeps working without connectivity for a
+//! limited grace period. Every entry is signed with HMAC-SHA256 using a secret
+//! from the configuration, so an edited cache file is rejected.
+//!
+//! For air-gapped machines, `verify_offline_token` checks a license token
+//! signed by the license server against its Ed25519 public key, without any
+//! network access.
+
+use crate::client::{LicenseInfo, ValidateResponse};
+use crate::device::hash_string;
+#[cfg(feature = "chrono")]
+use crate::errors::LycentoError;
+#[cfg(feature = "chrono")]
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+#[cfg(feature = "chrono")]
+use ed25519_dalek::{pkcs8::DecodePublicKey, Signature, VerifyingKey};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Configuration for the on-disk offline validation cache.
+#[derive(Clone)]
+pub struct OfflineCacheConfig {
+    /// Directory the cached validations are stored in.
+    pub directory: PathBuf,
+    /// Secret key used to sign cache entries.
+    pub secret: Vec<u8>,
+}
+
+impl fmt::Debug for OfflineCacheConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OfflineCacheConfig")
+            .field("directory", &self.directory)
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
+
+/// A validation loaded from the offline cache.
+#[derive(Debug, Clone)]
+pub(crate) struct CachedValidation {
+    /// The cached server response.
+    pub response: ValidateResponse,
+    /// When the response was received from the server.
+    pub validated_at: SystemTime,
+}
+
+/// Cache entry as written to disk (before signing).
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    license_key: String,
+    validated_at: u64,
+    response: ValidateResponse,
+}
+
+/// Signed envelope around a serialized [`CacheEntry`].
+#[derive(Serialize, Deserialize)]
+struct SignedEntry {
+    payload: String,
+    signature: String,
+}
+
+impl OfflineCacheConfig {
+    /// Create a new offline cache configuration.
+    pub fn new(directory: impl Into<PathBuf>, secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            directory: directory.into(),
+            secret: secret.into(),
+        }
+    }
+
+    /// Persist a validation response for `license_key`.
+    pub(crate) fn store(
+        &self,
+        license_key: &str,
+        response: &ValidateResponse,
+        validated_at: SystemTime,
+    ) -> io::Result<()> {
+        let entry = CacheEntry {
+            license_key: license_key.to_string(),
+            validated_at: validated_at
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            response: response.clone(),
+        };
+
+        let payload = serde_json::to_string(&entry)?;
+        let signed = SignedEntry {
+            signature: hex::encode(self.mac(payload.as_bytes()).finalize().into_bytes()),
+            payload,
+        };
+
+        fs::create_dir_all(&self.directory)?;
+        fs::write(self.entry_path(license_key), serde_json::to_vec(&signed)?)
+    }
+
+    /// Load the cached validation for `license_key`.
+    ///
+    /// Returns `None` if there is no entry or its signature doesn't match.
+    pub(crate) fn load(&self, license_key: &str) -> Option<CachedValidation> {
+        let contents = fs::read(self.entry_path(license_key)).ok()?;
+        let signed: SignedEntry = serde_json::from_slice(&contents).ok()?;
+
+        let signature = hex::decode(&signed.signature).ok()?;
+        self.mac(signed.payload.as_bytes())
+            .verify_slice(&signature)
+            .ok()?;
+
+        let entry: CacheEntry = serde_json::from_str(&signed.payload).ok()?;
+        if entry.license_key != license_key {
+            return None;
+        }
+
+        Some(CachedValidation {
+            response: entry.response,
+            validated_at: UNIX_EPOCH + Duration::from_secs(entry.validated_at),
+        })
+    }
+
+    fn entry_path(&self, license_key: &str) -> PathBuf {
+        self.directory
+            .join(format!("{}.json", &hash_string(license_key)[..32]))
+    }
+
+    fn mac(&self, payload: &[u8]) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(payload);
+        mac
+    }
+}
+
+/// A license decoded from a verified offline token.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfflineLicense {
+    /// License details from the token's claims.
+    pub license: LicenseInfo,
+}
+
+/// Header of an offline token.
+#[cfg(feature = "chrono")]
+#[derive(Deserialize)]
+struct TokenHeader {
+    alg: String,
+}
+
+/// Verify an offline license token and decode its license.
+///
+/// The token has the form `header.claims.signature`, each part base64url
+/// encoded without padding, like a JWT. The header must be
+/// `{"alg": "EdDSA"}`, the claims are a license in the shape the server
+/// returns (`key`, `status`, `type`, `expiresAt`, `maxDevices`), and the
+/// signature is the Ed25519 signature of `header.claims` by the key matching
+/// `public_key_pem` (an SPKI `PUBLIC KEY` PEM block).
+///
+/// Fails with [`LycentoError::SignatureMismatch`] if the signature doesn't
+/// verify and [`LycentoError::LicenseExpired`] if the license has expired.
+/// The license status is returned as-is. Requires the `chrono` feature
+/// (enabled by default).
+#[cfg(feature = "chrono")]
+pub fn verify_offline_token(token: &str, public_key_pem: &str) -> Result<OfflineLicense, LycentoError> {
+    let key = VerifyingKey::from_public_key_pem(public_key_pem)
+        .map_err(|e| LycentoError::InvalidToken(format!("invalid public key: {}", e)))?;
+
+    let mut parts = token.trim().split('.');
+    let (Some(header), Some(claims), Some(signature), None) = (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(LycentoError::InvalidToken("expected three dot-separated parts".to_string()));
+    };
+
+    let decode = |part: &str| {
+        URL_SAFE_NO_PAD
+            .decode(part)
+            .map_err(|e| LycentoError::InvalidToken(format!("invalid base64: {}", e)))
+    };
+
+    let token_header: TokenHeader = serde_json::from_slice(&decode(header)?)
+        .map_err(|e| LycentoError::InvalidToken(format!("invalid header: {}", e)))?;
+    if token_header.alg != "EdDSA" {
+        return Err(LycentoError::InvalidToken(format!("unsupported algorithm {}", token_header.alg)));
+    }
+
+    let signature = Signature::from_slice(&decode(signature)?).map_err(|_| LycentoError::SignatureMismatch)?;
+    key.verify_strict(format!("{}.{}", header, claims).as_bytes(), &signature)
+        .map_err(|_| LycentoError::SignatureMismatch)?;
+
+    let license: LicenseInfo = serde_json::from_slice(&decode(claims)?)
+        .map_err(|e| LycentoError::InvalidToken(format!("invalid claims: {}", e)))?;
+    match license.is_expired() {
+        Some(false) => Ok(OfflineLicense { license }),
+        Some(true) => Err(LycentoError::LicenseExpired {
+            expires_at: license.expires_at.unwrap_or_default(),
+        }),
+        None => Err(LycentoError::InvalidToken("unparseable expiresAt".to_string())),
+    }
+}
+
+impl CachedValidation {
+    /// Whether the cached validation is still within `grace_period`.
+    ///
+    /// A validation time in the future means the clock was set back or the
+    /// entry was written with a wrong clock, so it isn't trusted.
+    pub(crate) fn is_within(&self, grace_period: Duration) -> bool {
+        SystemTime::now()
+            .duration_since(self.validated_at)
+            .map(|age| age <= grace_period)
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_cache() -> OfflineCacheConfig {
+        let directory = std::env::temp_dir().join(format!("lycento-sdk-test-{}", uuid::Uuid::new_v4().simple()));
+        OfflineCacheConfig::new(directory, "secret")
+    }
+
+    fn test_response() -> ValidateResponse {
+        serde_json::from_value(serde_json::json!({
+            "valid": true,
+            "license": {
+                "key": "TEST-KEY",
+                "status": "active",
+                "type": "perpetual",
+                "expiresAt": null,
+                "maxDevices": 3
+            }
+        }))
+        .unwrap()
+    }
+
+    #[cfg(feature = "chrono")]
+    fn sign_token(key: &ed25519_dalek::SigningKey, claims: serde_json::Value) -> String {
+        use ed25519_dalek::Signer;
+
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"EdDSA"}"#);
+        let claims = URL_SAFE_NO_PAD.encode(claims.to_string());
+        let signature = key.sign(format!("{}.{}", header, claims).as_bytes());
+        format!("{}.{}.{}", header, claims, URL_SAFE_NO_PAD.encode(signature.to_bytes()))
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_verify_offline_token() {
+        use ed25519_dalek::pkcs8::{spki::der::pem::LineEnding, EncodePublicKey};
+
+        let key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+        let public_key_pem = key.verifying_key().to_public_key_pem(LineEnding::LF).unwrap();
+        let claims = serde_json::json!({
+            "key": "TEST-KEY",
+            "status": "active",
+            "type": "subscription",
+            "expiresAt": "2999-01-01T00:00:00Z",
+            "maxDevices": 2
+        });
+
+        let token = sign_token(&key, claims.clone());
+        let offline = verify_offline_token(&token, &public_key_pem).unwrap();
+        assert_eq!(offline.license.key, "TEST-KEY");
+        assert_eq!(offline.license.max_devices, 2);
+
+        let other_key = ed25519_dalek::SigningKey::from_bytes(&[8; 32]);
+        let forged = sign_token(&other_key, claims.clone());
+        assert!(matches!(verify_offline_token(&forged, &public_key_pem), Err(LycentoError::SignatureMismatch)));
+
+        let parts: Vec<_> = token.split('.').collect();
+        let tampered = format!("{}.{}.{}", parts[0], URL_SAFE_NO_PAD.encode(r#"{"maxDevices":99}"#), parts[2]);
+        assert!(matches!(verify_offline_token(&tampered, &public_key_pem), Err(LycentoError::SignatureMismatch)));
+
+        let mut expired = claims;
+        expired["expiresAt"] = "2000-01-01T00:00:00Z".into();
+        let expired = sign_token(&key, expired);
+        assert!(matches!(verify_offline_token(&expired, &public_key_pem), Err(LycentoError::LicenseExpired { .. })));
+
+        assert!(matches!(verify_offline_token("not-a-token", &public_key_pem), Err(LycentoError::InvalidToken(_))));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cache = test_cache();
+        cache.store("TEST-KEY", &test_response(), SystemTime::now()).unwrap();
+
+        let cached = cache.load("TEST-KEY").unwrap();
+        assert!(cached.response.valid);
+        assert!(cached.is_within(Duration::from_secs(60)));
+        assert!(cache.load("OTHER-KEY").is_none());
+
+        let _ = fs::remove_dir_all(&cache.directory);
+    }
+
+    #[test]
+    fn test_rejects_tampered_entry() {
+        let cache = test_cache();
+        cache.store("TEST-KEY", &test_response(), SystemTime::now()).unwrap();
+
+        let path = cache.entry_path("TEST-KEY");
+        let tampered = fs::read_to_string(&path)
+            .unwrap()
+            .replace(r#"\"valid\":true"#, r#"\"valid\":false"#);
+        fs::write(&path, tampered).unwrap();
+        assert!(cache.load("TEST-KEY").is_none());
+
+        let other = OfflineCacheConfig::new(cache.directory.clone(), "other-secret");
+        cache.store("TEST-KEY", &test_response(), SystemTime::now()).unwrap();
+        assert!(other.load("TEST-KEY").is_none());
+
+        let _ = fs::remove_dir_all(&cache.directory);
+    }
+
+    #[test]
+    fn test_grace_period() {
+        let cache = test_cache();
+        let an_hour_ago = SystemTime::now() - Duration::from_secs(3600);
+        cache.store("TEST-KEY", &test_response(), an_hour_ago).unwrap();
+
+        let cached = cache.load("TEST-KEY").unwrap();
+        assert!(!cached.is_within(Duration::from_secs(60)));
+        assert!(cached.is_within(Duration::from_secs(7200)));
+
+        // Validated "in the future", e.g. after the clock was turned back
+        let in_an_hour = SystemTime::now() + Duration::from_secs(3600);
+        cache.store("TEST-KEY", &test_response(), in_an_hour).unwrap();
+        assert!(!cache.load("TEST-KEY").unwrap().is_within(Duration::from_secs(7200)));
+
+        let _ = fs::remove_dir_all(&cache.directory);
+    }
+}
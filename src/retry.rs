@@ -0,0 +1,162 @@
+//! Retry helpers for the Lycento SDK.
+//!
+//! This module decides which failures are worth retrying and computes the
+//! exponential backoff (with jitter) used between attempts, unless the server
+//! asks for a specific delay through `Retry-After`.
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Upper bound for a single backoff delay.
+pub(crate) const MAX_BACKOFF_MS: u64 = 30_000;
+
+/// Whether an HTTP status is transient and worth retrying.
+///
+/// Rate limiting (429) and server errors (5xx) are retried; every other
+/// status (including 404 and 422) is deterministic and returned as-is.
+pub(crate) fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Whether a transport error is transient and worth retrying.
+pub(crate) fn is_retryable_error(error: &reqwest::Error) -> bool {
+    error.is_timeout() || is_connect(error)
+}
+
+/// Whether a transport error happened while connecting.
+///
+/// WASM builds can't tell, since fetch doesn't report connection errors.
+pub(crate) fn is_connect(error: &reqwest::Error) -> bool {
+    #[cfg(not(target_arch = "wasm32"))]
+    return error.is_connect();
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = error;
+        false
+    }
+}
+
+/// Compute the delay before the next attempt.
+///
+/// `attempt` is the 1-based number of the attempt that just failed. The delay
+/// doubles with every attempt and a random jitter of up to half the delay is
+/// added so that many clients don't retry in lockstep.
+pub(crate) fn backoff_delay(base_delay_ms: u64, attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16);
+    let delay = base_delay_ms
+        .saturating_mul(1u64 << exponent)
+        .min(MAX_BACKOFF_MS);
+
+    Duration::from_millis(delay + jitter(delay / 2))
+}
+
+/// Parse the `Retry-After` header as either delta-seconds or an HTTP date.
+///
+/// Dates in the past yield a zero delay.
+pub(crate) fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    // `SystemTime::now` panics on WASM.
+    if cfg!(target_arch = "wasm32") {
+        return None;
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO))
+}
+
+/// Delay before retrying a response with a retryable status.
+///
+/// Honors `Retry-After` when present, capped at the backoff cap so a server
+/// asking for a long wait doesn't block the call indefinitely. If the retries
+/// run out, the caller still gets the full delay in
+/// [`LycentoError::RateLimited`](crate::LycentoError::RateLimited).
+pub(crate) fn retry_delay(headers: &HeaderMap, base_delay_ms: u64, attempt: u32) -> Duration {
+    match parse_retry_after(headers) {
+        Some(delay) => delay.min(Duration::from_millis(MAX_BACKOFF_MS)),
+        None => backoff_delay(base_delay_ms, attempt),
+    }
+}
+
+/// Wait `delay` before the next attempt.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn sleep(delay: Duration) {
+    tokio::time::sleep(delay).await;
+}
+
+/// Retries are disabled on WASM, which has no tokio timer, so this is never
+/// reached there.
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep(_delay: Duration) {}
+
+/// Cheap pseudo-random jitter in `0..=max` milliseconds.
+fn jitter(max: u64) -> u64 {
+    if max == 0 {
+        return 0;
+    }
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+
+    u64::from(nanos) % (max + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retryable_statuses() {
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+        assert!(!is_retryable_status(StatusCode::UNPROCESSABLE_ENTITY));
+    }
+
+    fn retry_after(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        assert_eq!(parse_retry_after(&retry_after("7")), Some(Duration::from_secs(7)));
+        assert_eq!(parse_retry_after(&retry_after("soon")), None);
+        assert_eq!(parse_retry_after(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() {
+        let date = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(120));
+        let delay = parse_retry_after(&retry_after(&date)).unwrap();
+        assert!(delay > Duration::from_secs(110) && delay <= Duration::from_secs(120));
+
+        let past = retry_after("Wed, 21 Oct 2015 07:28:00 GMT");
+        assert_eq!(parse_retry_after(&past), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_retry_delay_caps_long_retry_after() {
+        assert_eq!(retry_delay(&retry_after("2"), 100, 1), Duration::from_secs(2));
+        assert_eq!(retry_delay(&retry_after("3600"), 100, 1), Duration::from_millis(MAX_BACKOFF_MS));
+        assert!(retry_delay(&HeaderMap::new(), 100, 1) <= Duration::from_millis(150));
+    }
+
+    #[test]
+    fn test_backoff_grows_exponentially() {
+        for attempt in 1..=4 {
+            let expected = 100 * (1u64 << (attempt - 1));
+            let delay = backoff_delay(100, attempt).as_millis() as u64;
+            assert!(delay >= expected && delay <= expected + expected / 2);
+        }
+    }
+}
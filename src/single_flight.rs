@@ -0,0 +1,128 @@
+//! Deduplication of concurrent identical validations.
+//!
+//! While a validation of a `(license_key, device_id)` pair is in flight,
+//! further validations of the same pair don't send their own request: they
+//! wait for the first one and receive a copy of its result. Shared by all
+//! clones of a client.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+
+type Key = (String, String);
+
+/// In-flight calls keyed by license key and device ID.
+#[derive(Debug)]
+pub(crate) struct SingleFlight<T> {
+    calls: Mutex<HashMap<Key, watch::Receiver<Option<Arc<T>>>>>,
+}
+
+/// A caller's role in a call.
+pub(crate) enum Flight<'a, T> {
+    /// No call was in flight; this caller makes it.
+    Leader(Leader<'a, T>),
+    /// Another caller is making the call.
+    Follower(Follower<T>),
+}
+
+/// Makes the call and shares its result with the followers.
+///
+/// The call is forgotten when the leader is dropped, so followers of a
+/// cancelled leader stop waiting.
+pub(crate) struct Leader<'a, T> {
+    flights: &'a SingleFlight<T>,
+    key: Key,
+    sender: watch::Sender<Option<Arc<T>>>,
+    receiver: watch::Receiver<Option<Arc<T>>>,
+}
+
+/// Waits for the leader's result.
+pub(crate) struct Follower<T> {
+    receiver: watch::Receiver<Option<Arc<T>>>,
+}
+
+impl<T> SingleFlight<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Join the call for `license_key` on `device_id`, starting it if none is in flight.
+    pub(crate) fn join(&self, license_key: &str, device_id: &str) -> Flight<'_, T> {
+        let mut calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (license_key.to_string(), device_id.to_string());
+
+        if let Some(receiver) = calls.get(&key) {
+            return Flight::Follower(Follower {
+                receiver: receiver.clone(),
+            });
+        }
+
+        let (sender, receiver) = watch::channel(None);
+        calls.insert(key.clone(), receiver.clone());
+        Flight::Leader(Leader {
+            flights: self,
+            key,
+            sender,
+            receiver,
+        })
+    }
+}
+
+impl<T> Leader<'_, T> {
+    /// Hand the call's result to the followers.
+    pub(crate) fn finish(self, result: T) {
+        self.sender.send_replace(Some(Arc::new(result)));
+    }
+}
+
+impl<T> Drop for Leader<'_, T> {
+    fn drop(&mut self) {
+        let mut calls = self.flights.calls.lock().unwrap_or_else(|e| e.into_inner());
+        if calls.get(&self.key).is_some_and(|receiver| receiver.same_channel(&self.receiver)) {
+            calls.remove(&self.key);
+        }
+    }
+}
+
+impl<T> Follower<T> {
+    /// The leader's result, or `None` if the leader was cancelled.
+    pub(crate) async fn wait(mut self) -> Option<Arc<T>> {
+        let result = self.receiver.wait_for(Option::is_some).await.ok()?;
+        result.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_followers_share_the_leader_result() {
+        let flights = SingleFlight::new();
+        let Flight::Leader(leader) = flights.join("TEST-KEY", "device-1") else {
+            panic!("expected to lead the first call");
+        };
+        let Flight::Follower(follower) = flights.join("TEST-KEY", "device-1") else {
+            panic!("expected to follow the call in flight");
+        };
+        assert!(matches!(flights.join("TEST-KEY", "device-2"), Flight::Leader(_)));
+
+        leader.finish(42);
+        assert_eq!(follower.wait().await.as_deref(), Some(&42));
+        assert!(matches!(flights.join("TEST-KEY", "device-1"), Flight::Leader(_)));
+    }
+
+    #[tokio::test]
+    async fn test_followers_stop_waiting_for_a_cancelled_leader() {
+        let flights = SingleFlight::<u32>::new();
+        let leader = flights.join("TEST-KEY", "device-1");
+        let Flight::Follower(follower) = flights.join("TEST-KEY", "device-1") else {
+            panic!("expected to follow the call in flight");
+        };
+
+        drop(leader);
+        assert_eq!(follower.wait().await, None);
+    }
+}
@@ -0,0 +1,193 @@
+//! Tauri integration for the Lycento SDK.
+//!
+//! This module provides `LycentoPlugin`, which registers a `LycentoClient` as
+//! Tauri state together with ready-made commands, and `CommandError`, the
+//! serializable error those commands return. It is only available with the
+//! `tauri` feature.
+//!
+//! ```rust,ignore
+//! tauri::Builder::default()
+//!     .plugin(LycentoPlugin::new(LycentoConfig::new("https://lycento.test")).build())
+//!     .run(tauri::generate_context!())
+//!     .expect("error while running tauri application");
+//! ```
+//!
+//! From the frontend the commands are invoked through the plugin, e.g.
+//! `invoke("plugin:lycento|lycento_validate", { licenseKey })`.
+
+use crate::client::{
+    ActivateOptions, ActivateResponse, DeactivateOptions, DeactivateResponse, LicenseInfoResponse, LycentoClient,
+    LycentoConfig, ValidateResponse,
+};
+use crate::errors::{ActivationError, DeactivationError, LycentoError, ValidationError};
+use serde::Serialize;
+use tauri::plugin::{Builder, TauriPlugin};
+use tauri::{Manager, Runtime, State};
+
+/// Error returned to the frontend by the Lycento commands.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandError {
+    /// Error category: `activation`, `validation`, `deactivation`, `network` or `other`.
+    pub kind: &'static str,
+    /// Human-readable message.
+    pub message: String,
+    /// HTTP status returned by the server, if any.
+    pub status: Option<u16>,
+}
+
+impl CommandError {
+    fn new(kind: &'static str, message: &str) -> Self {
+        Self {
+            kind,
+            message: message.to_string(),
+            status: None,
+        }
+    }
+}
+
+impl From<LycentoError> for CommandError {
+    fn from(err: LycentoError) -> Self {
+        let kind = if err.is_network() { "network" } else { "other" };
+        Self {
+            status: err.status(),
+            ..Self::new(kind, err.message())
+        }
+    }
+}
+
+// Network and HTTP failures are reported like a `LycentoError`, with their
+// status, whichever operation they came from.
+
+impl From<ActivationError> for CommandError {
+    fn from(err: ActivationError) -> Self {
+        match err {
+            ActivationError::Network(_) | ActivationError::Request(_) => LycentoError::from(err).into(),
+            err => Self::new("activation", err.message()),
+        }
+    }
+}
+
+impl From<ValidationError> for CommandError {
+    fn from(err: ValidationError) -> Self {
+        match err {
+            ValidationError::Network(_) | ValidationError::Request(_) => LycentoError::from(err).into(),
+            err => Self::new("validation", err.message()),
+        }
+    }
+}
+
+impl From<DeactivationError> for CommandError {
+    fn from(err: DeactivationError) -> Self {
+        match err {
+            DeactivationError::Network(_) | DeactivationError::Request(_) => LycentoError::from(err).into(),
+            err => Self::new("deactivation", err.message()),
+        }
+    }
+}
+
+/// Activate a license on this device.
+#[tauri::command]
+pub async fn lycento_activate(
+    client: State<'_, LycentoClient>,
+    license_key: String,
+    device_name: Option<String>,
+) -> Result<ActivateResponse, CommandError> {
+    let mut options = ActivateOptions::new(license_key);
+    if let Some(device_name) = device_name {
+        options = options.with_device_name(device_name);
+    }
+
+    Ok(client.activate(options).await?)
+}
+
+/// Validate a license on this device.
+#[tauri::command]
+pub async fn lycento_validate(
+    client: State<'_, LycentoClient>,
+    license_key: String,
+) -> Result<ValidateResponse, CommandError> {
+    Ok(client.validate_license(&license_key).await?)
+}
+
+/// Deactivate a device, or this device if `device_id` is omitted.
+#[tauri::command]
+pub async fn lycento_deactivate(
+    client: State<'_, LycentoClient>,
+    license_key: String,
+    device_id: Option<String>,
+) -> Result<DeactivateResponse, CommandError> {
+    let response = match device_id {
+        Some(device_id) => client.deactivate(DeactivateOptions::new(license_key, device_id)).await?,
+        None => client.deactivate_current(&license_key).await?,
+    };
+
+    Ok(response)
+}
+
+/// Get license information including all activations.
+#[tauri::command]
+pub async fn lycento_get_info(
+    client: State<'_, LycentoClient>,
+    license_key: String,
+) -> Result<LicenseInfoResponse, CommandError> {
+    Ok(client.get_info(&license_key).await?)
+}
+
+/// Tauri plugin registering a `LycentoClient` and the Lycento commands.
+#[derive(Debug, Clone)]
+pub struct LycentoPlugin {
+    config: LycentoConfig,
+}
+
+impl LycentoPlugin {
+    /// Create a plugin whose client is built from `config`.
+    pub fn new(config: LycentoConfig) -> Self {
+        Self { config }
+    }
+
+    /// Build the plugin for `app.plugin(...)`.
+    ///
+    /// The client is created during plugin setup; an invalid configuration
+    /// makes the setup (and thus app startup) fail.
+    pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
+        Builder::new("lycento")
+            .invoke_handler(tauri::generate_handler![
+                lycento_activate,
+                lycento_validate,
+                lycento_deactivate,
+                lycento_get_info
+            ])
+            .setup(move |app, _api| {
+                app.manage(LycentoClient::new(self.config.clone())?);
+                Ok(())
+            })
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::NetworkError;
+
+    #[test]
+    fn test_command_error_kinds() {
+        let conflict = LycentoError::Http {
+            status: 409,
+            message: "Device limit reached".to_string(),
+            reason: None,
+        };
+        let error = CommandError::from(ActivationError::from(conflict));
+        assert_eq!((error.kind, error.status), ("other", Some(409)));
+
+        let error = CommandError::from(ValidationError::Network(NetworkError::new("offline")));
+        assert_eq!((error.kind, error.status), ("network", None));
+
+        let error = CommandError::from(DeactivationError::Network(NetworkError::new("offline")));
+        assert_eq!(error.kind, "network");
+
+        let error = CommandError::from(DeactivationError::Custom("not activated".to_string()));
+        assert_eq!((error.kind, error.message.as_str()), ("deactivation", "not activated"));
+    }
+}
@@ -0,0 +1,152 @@
+//! Short-lived access tokens, e.g. from OAuth, used instead of a static API
+//! key (see [`LycentoConfig::with_token_provider`](crate::LycentoConfig::with_token_provider)).
+
+use crate::errors::LycentoError;
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+use tokio::sync::Mutex;
+
+/// How long before its expiry a token is refreshed, at most half its lifetime.
+#[cfg(not(target_arch = "wasm32"))]
+const REFRESH_MARGIN: Duration = Duration::from_secs(30);
+
+/// An access token and how long it stays valid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessToken {
+    /// The token, sent like an API key.
+    pub value: String,
+    /// How long the token stays valid from now. Tokens without a lifetime
+    /// are reused until the server rejects them with 401.
+    pub expires_in: Option<Duration>,
+}
+
+impl AccessToken {
+    /// Create a token without a known lifetime.
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            expires_in: None,
+        }
+    }
+
+    /// Set how long the token stays valid from now, e.g. from an OAuth
+    /// `expires_in` field.
+    pub fn with_expires_in(mut self, expires_in: Duration) -> Self {
+        self.expires_in = Some(expires_in);
+        self
+    }
+}
+
+impl From<String> for AccessToken {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Source of access tokens, asked for a new one when the cached token is
+/// about to expire.
+///
+/// Calls are serialized per client, so a token is fetched once even when
+/// many requests need it at the same time. An error fails the request that
+/// needed the token.
+///
+/// On WASM the returned futures are not `Send`, and tokens with an
+/// [`expires_in`](AccessToken::expires_in) are fetched for every request,
+/// since there is no monotonic clock to expire them with.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait TokenProvider: Send + Sync {
+    /// Fetch a fresh access token.
+    async fn token(&self) -> Result<AccessToken, LycentoError>;
+}
+
+/// Provider set with [`LycentoConfig::with_token_provider`](crate::LycentoConfig::with_token_provider).
+#[derive(Clone)]
+pub struct TokenSource(pub(crate) Arc<dyn TokenProvider>);
+
+impl std::fmt::Debug for TokenSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TokenSource(..)")
+    }
+}
+
+/// The current token of a provider, refreshed shortly before it expires.
+pub(crate) struct TokenCache {
+    source: TokenSource,
+    cached: Mutex<Option<CachedToken>>,
+}
+
+struct CachedToken {
+    value: String,
+    #[cfg(not(target_arch = "wasm32"))]
+    refresh_at: Option<Instant>,
+}
+
+impl CachedToken {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn new(token: AccessToken) -> Option<Self> {
+        let refresh_at = token
+            .expires_in
+            .map(|ttl| Instant::now() + ttl.saturating_sub(REFRESH_MARGIN.min(ttl / 2)));
+        Some(Self {
+            value: token.value,
+            refresh_at,
+        })
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn new(token: AccessToken) -> Option<Self> {
+        token.expires_in.is_none().then_some(Self { value: token.value })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn is_fresh(&self) -> bool {
+        self.refresh_at.map_or(true, |refresh_at| Instant::now() < refresh_at)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn is_fresh(&self) -> bool {
+        true
+    }
+}
+
+impl TokenCache {
+    pub(crate) fn new(source: TokenSource) -> Self {
+        Self {
+            source,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// The cached token, or a new one from the provider if it's stale.
+    pub(crate) async fn token(&self) -> Result<String, LycentoError> {
+        let mut cached = self.cached.lock().await;
+        if let Some(token) = cached.as_ref().filter(|token| token.is_fresh()) {
+            return Ok(token.value.clone());
+        }
+
+        log::debug!("Fetching a new access token");
+        let token = self.source.0.token().await?;
+        let value = token.value.clone();
+        *cached = CachedToken::new(token);
+        Ok(value)
+    }
+
+    /// Forget the cached token after the server rejected `rejected`, unless
+    /// it was already replaced by a newer one.
+    pub(crate) async fn invalidate(&self, rejected: &str) {
+        let mut cached = self.cached.lock().await;
+        if cached.as_ref().is_some_and(|token| token.value == rejected) {
+            *cached = None;
+        }
+    }
+}
+
+impl std::fmt::Debug for TokenCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TokenCache(..)")
+    }
+}